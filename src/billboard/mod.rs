@@ -0,0 +1,254 @@
+use std::f32::consts::TAU;
+
+use bevy::{gltf::GltfExtras, math::Affine2, prelude::*};
+use serde::Deserialize;
+
+use crate::GameplaySet;
+
+mod shadow;
+mod sprite;
+pub use shadow::*;
+pub use sprite::*;
+
+pub struct BillboardPlugin;
+
+impl Plugin for BillboardPlugin {
+    fn build(&self, app: &mut bevy::prelude::App) {
+        app.init_resource::<BillboardSpriteCache>()
+            .add_systems(Startup, setup_blob_shadow_assets)
+            .add_systems(
+                Update,
+                (
+                    update_billboard_frames,
+                    (spawn_blob_shadows, update_blob_shadows).chain(),
+                    spawn_cutout_shadows,
+                )
+                    .in_set(GameplaySet),
+            )
+            .add_systems(
+                PostUpdate,
+                (update_billboards, update_cutout_shadows)
+                    .chain()
+                    .in_set(GameplaySet)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// How a billboard is turned towards the camera.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum BillboardMode {
+    /// Parallel to the screen.
+    #[default]
+    ScreenAligned,
+    /// Turned towards the camera's position on every axis.
+    Spherical,
+    /// Turned towards the camera's position around the Y axis only, staying upright.
+    Cylindrical,
+    /// Left as it is, for entities only using the sprite sheet.
+    None,
+}
+
+/// Number of views in a directional sprite sheet.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpriteDirections {
+    Four,
+    Eight,
+}
+
+impl SpriteDirections {
+    pub fn from_count(count: u32) -> Option<Self> {
+        match count {
+            4 => Some(Self::Four),
+            8 => Some(Self::Eight),
+            _ => None,
+        }
+    }
+
+    pub fn count(&self) -> usize {
+        match self {
+            Self::Four => 4,
+            Self::Eight => 8,
+        }
+    }
+}
+
+/// Turns an entity's texture towards the frontmost active 3D camera, in world space even when its
+/// parent is rotated.
+///
+/// With `directions` set, the texture is a sprite sheet with one column per view, going
+/// counter-clockwise around the entity seen from above: for four directions the columns show its
+/// front, left side, back and right side. Animation frames are stacked in rows below each other.
+#[derive(Component, Debug, Default)]
+pub struct Billboard {
+    pub mode: BillboardMode,
+    pub directions: Option<SpriteDirections>,
+    /// Horizontal direction the entity faces in world space, separate from its rotation towards
+    /// the camera.
+    pub facing: Vec3,
+    /// Row of the sprite sheet showing the current animation frame, out of `rows`.
+    pub row: usize,
+    pub rows: usize,
+}
+
+impl Billboard {
+    pub fn with_mode(mut self, mode: BillboardMode) -> Self {
+        self.mode = mode;
+        self
+    }
+
+    pub fn with_directions(mut self, directions: Option<SpriteDirections>) -> Self {
+        self.directions = directions;
+        self
+    }
+
+    pub fn with_facing(mut self, facing: impl Into<Vec3>) -> Self {
+        self.facing = facing.into();
+        self
+    }
+
+    /// Sprite sheet column showing the entity from `view`, the direction from it to the camera.
+    pub fn direction_index(&self, view: Vec3) -> usize {
+        let Some(directions) = self.directions else {
+            return 0;
+        };
+        // Angle counter-clockwise around the Y axis, with zero along -Z
+        let yaw = |direction: Vec3| f32::atan2(-direction.x, -direction.z);
+        let count = directions.count();
+        let step = TAU / count as f32;
+        ((yaw(view) - yaw(self.facing)) / step)
+            .round()
+            .rem_euclid(count as f32) as usize
+    }
+
+    fn uv_transform(&self, view: Vec3) -> Affine2 {
+        let columns = self.directions.map_or(1, |directions| directions.count());
+        let cell_size = Vec2::new(1.0 / columns as f32, 1.0 / self.rows.max(1) as f32);
+        Affine2::from_scale_angle_translation(
+            cell_size,
+            0.0,
+            Vec2::new(self.direction_index(view) as f32, self.row as f32) * cell_size,
+        )
+    }
+}
+
+/// Properties of a billboard spawned from a glTF node.
+#[derive(Debug, Default, Deserialize)]
+pub struct BillboardExtras {
+    pub billboard_mode: Option<BillboardMode>,
+    /// File name of embedded sprite animations to use in place of the default ones.
+    pub sprite_animations: Option<String>,
+    /// Number of directional columns in the sprite sheet, 4 or 8.
+    pub sprite_directions: Option<u32>,
+    pub sprite_shadow: Option<SpriteShadow>,
+}
+
+impl BillboardExtras {
+    pub fn from_extras(extras: &GltfExtras) -> Self {
+        serde_json::from_str(&extras.value).unwrap_or_default()
+    }
+
+    pub fn directions(&self) -> Option<SpriteDirections> {
+        let count = self.sprite_directions?;
+        let directions = SpriteDirections::from_count(count);
+        if directions.is_none() {
+            warn!("Sprite sheets have 4 or 8 directions, not {}", count);
+        }
+        directions
+    }
+}
+
+fn active_camera(camera_query: &Query<(Entity, &Camera), With<Camera3d>>) -> Option<Entity> {
+    camera_query
+        .iter()
+        .filter(|(_, camera)| camera.is_active)
+        .max_by_key(|(_, camera)| camera.order)
+        .map(|(entity, _)| entity)
+}
+
+/// Composes transforms up the hierarchy, since global transforms are only propagated after this
+/// frame's movement.
+fn world_transform(
+    entity: Entity,
+    transform_query: &Query<(&mut Transform, Option<&Parent>)>,
+) -> Transform {
+    let mut world_transform = Transform::IDENTITY;
+    let mut next = Some(entity);
+    while let Some(entity) = next {
+        let Ok((transform, parent)) = transform_query.get(entity) else {
+            break;
+        };
+        world_transform = *transform * world_transform;
+        next = parent.map(Parent::get);
+    }
+    world_transform
+}
+
+fn update_billboards(
+    camera_query: Query<(Entity, &Camera), With<Camera3d>>,
+    billboard_query: Query<(Entity, &Billboard)>,
+    mut transform_query: Query<(&mut Transform, Option<&Parent>)>,
+) {
+    let Some(camera_entity) = active_camera(&camera_query) else {
+        return;
+    };
+    let camera_transform = world_transform(camera_entity, &transform_query);
+    for (entity, billboard) in billboard_query.iter() {
+        let translation = world_transform(entity, &transform_query).translation;
+        let rotation = match billboard.mode {
+            BillboardMode::ScreenAligned => {
+                Transform::IDENTITY
+                    .looking_to(camera_transform.back(), camera_transform.up())
+                    .rotation
+            }
+            BillboardMode::Spherical => {
+                Transform::from_translation(translation)
+                    .looking_at(camera_transform.translation, Vec3::Y)
+                    .rotation
+            }
+            BillboardMode::Cylindrical => {
+                Transform::from_translation(translation)
+                    .looking_at(camera_transform.translation.with_y(translation.y), Vec3::Y)
+                    .rotation
+            }
+            BillboardMode::None => continue,
+        };
+        let Ok((_, parent)) = transform_query.get(entity) else {
+            continue;
+        };
+        let parent_rotation = parent.map_or(Quat::IDENTITY, |parent| {
+            world_transform(parent.get(), &transform_query).rotation
+        });
+        if let Ok((mut transform, _)) = transform_query.get_mut(entity) {
+            transform.rotation = parent_rotation.inverse() * rotation;
+        }
+    }
+}
+
+fn update_billboard_frames(
+    camera_query: Query<(Entity, &Camera), With<Camera3d>>,
+    global_transform_query: Query<&GlobalTransform>,
+    mut billboard_query: Query<(&Billboard, &GlobalTransform, &mut Handle<StandardMaterial>)>,
+    mut sprites: BillboardSprites,
+) {
+    let Some(camera_translation) = active_camera(&camera_query)
+        .and_then(|entity| global_transform_query.get(entity).ok())
+        .map(GlobalTransform::translation)
+    else {
+        return;
+    };
+    for (billboard, billboard_transform, mut material) in billboard_query.iter_mut() {
+        let uv_transform =
+            billboard.uv_transform(camera_translation - billboard_transform.translation());
+        // Compare with the material itself, since highlighting swaps it for a copy
+        if sprites
+            .material(&material)
+            .is_some_and(|material| material.uv_transform != uv_transform)
+        {
+            if let Some(material) = sprites.material_mut(&mut material) {
+                material.uv_transform = uv_transform;
+            }
+        }
+    }
+}
@@ -0,0 +1,235 @@
+use avian3d::spatial_query::{SpatialQuery, SpatialQueryFilter};
+use bevy::{
+    pbr::{NotShadowCaster, NotShadowReceiver},
+    prelude::*,
+    render::{
+        render_asset::RenderAssetUsages,
+        render_resource::{Extent3d, TextureDimension, TextureFormat},
+        view::RenderLayers,
+    },
+};
+use serde::Deserialize;
+
+use super::world_transform;
+use crate::GameObject;
+
+/// Render layer seen only by lights, for meshes that should cast shadows without being drawn.
+pub const SHADOW_PROXY_LAYER: usize = 1;
+
+/// Height above the shadow caster's origin the ground is searched from.
+const RAY_START_HEIGHT: f32 = 0.25;
+/// Height above the ground at which a blob shadow has shrunk away completely.
+const BLOB_SHADOW_MAX_HEIGHT: f32 = 4.0;
+/// Distance the shadow is lifted off the ground to avoid z-fighting.
+const BLOB_SHADOW_OFFSET: f32 = 0.02;
+const BLOB_SHADOW_TEXTURE_SIZE: u32 = 64;
+const BLOB_SHADOW_OPACITY: f32 = 0.5;
+/// Alpha below which a cutout shadow lets light through.
+const CUTOUT_ALPHA_THRESHOLD: f32 = 0.5;
+
+/// Kind of shadow a billboard sprite spawned from a glTF node casts.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SpriteShadow {
+    None,
+    #[default]
+    Blob,
+    Cutout,
+}
+
+/// Draws a soft round shadow on the ground below the entity's origin, which is the feet of a
+/// billboard anchored at its bottom.
+#[derive(Component, Debug)]
+pub struct BlobShadow {
+    pub radius: f32,
+}
+
+/// Casts the silhouette of the sprite from directional lights, through an alpha-masked copy of it
+/// on `SHADOW_PROXY_LAYER` that turns to face the light.
+#[derive(Component, Debug)]
+pub struct CutoutShadow;
+
+#[derive(Component, Debug)]
+pub struct ShadowProxy;
+
+/// The shadow drawn for a `BlobShadow` entity.
+#[derive(Component, Debug)]
+pub struct BlobShadowDecal {
+    caster: Entity,
+}
+
+#[derive(Resource, Debug)]
+pub(super) struct BlobShadowAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+pub(super) fn setup_blob_shadow_assets(
+    mut commands: Commands,
+    mut images: ResMut<Assets<Image>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let size = BLOB_SHADOW_TEXTURE_SIZE;
+    let data = (0..size * size)
+        .flat_map(|index| {
+            let position = Vec2::new((index % size) as f32, (index / size) as f32) + 0.5;
+            let distance = (position / size as f32 * 2.0 - 1.0).length();
+            let falloff = (1.0 - distance).clamp(0.0, 1.0);
+            let alpha = falloff * falloff * BLOB_SHADOW_OPACITY;
+            [0, 0, 0, (alpha * 255.0) as u8]
+        })
+        .collect();
+    let texture = images.add(Image::new(
+        Extent3d {
+            width: size,
+            height: size,
+            depth_or_array_layers: 1,
+        },
+        TextureDimension::D2,
+        data,
+        TextureFormat::Rgba8UnormSrgb,
+        RenderAssetUsages::RENDER_WORLD,
+    ));
+
+    commands.insert_resource(BlobShadowAssets {
+        mesh: meshes.add(Plane3d::new(Vec3::Y, Vec2::ONE)),
+        material: materials.add(StandardMaterial {
+            base_color_texture: Some(texture),
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..Default::default()
+        }),
+    });
+}
+
+pub(super) fn spawn_blob_shadows(
+    caster_query: Query<Entity, Added<BlobShadow>>,
+    assets: Res<BlobShadowAssets>,
+    mut commands: Commands,
+) {
+    for caster in caster_query.iter() {
+        commands.spawn((
+            GameObject,
+            BlobShadowDecal { caster },
+            PbrBundle {
+                mesh: assets.mesh.clone(),
+                material: assets.material.clone(),
+                visibility: Visibility::Hidden,
+                ..Default::default()
+            },
+            NotShadowCaster,
+            NotShadowReceiver,
+        ));
+    }
+}
+
+pub(super) fn update_blob_shadows(
+    caster_query: Query<(&BlobShadow, &GlobalTransform, Option<&Parent>)>,
+    mut decal_query: Query<(Entity, &BlobShadowDecal, &mut Transform, &mut Visibility)>,
+    spatial_query: SpatialQuery,
+    mut commands: Commands,
+) {
+    for (decal_entity, decal, mut decal_transform, mut visibility) in decal_query.iter_mut() {
+        let Ok((blob_shadow, caster_transform, parent)) = caster_query.get(decal.caster) else {
+            commands.entity(decal_entity).despawn_recursive();
+            continue;
+        };
+
+        // Skip the collider of a character whose sprite is a child
+        let excluded = [Some(decal.caster), parent.map(Parent::get)];
+        let origin = caster_transform.translation() + Vec3::Y * RAY_START_HEIGHT;
+        let hit = spatial_query.cast_ray(
+            origin,
+            Dir3::NEG_Y,
+            RAY_START_HEIGHT + BLOB_SHADOW_MAX_HEIGHT,
+            true,
+            SpatialQueryFilter::from_excluded_entities(excluded.into_iter().flatten()),
+        );
+        let Some(hit) = hit else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+
+        let height = (hit.time_of_impact - RAY_START_HEIGHT).max(0.0);
+        let scale = blob_shadow.radius * (1.0 - height / BLOB_SHADOW_MAX_HEIGHT);
+        *visibility = Visibility::Inherited;
+        *decal_transform = Transform {
+            translation: origin - Vec3::Y * hit.time_of_impact + hit.normal * BLOB_SHADOW_OFFSET,
+            rotation: Quat::from_rotation_arc(Vec3::Y, hit.normal),
+            scale: Vec3::splat(scale),
+        };
+    }
+}
+
+pub(super) fn spawn_cutout_shadows(
+    caster_query: Query<(Entity, &Handle<Mesh>), Added<CutoutShadow>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    for (caster, mesh) in caster_query.iter() {
+        let proxy = commands
+            .spawn((
+                ShadowProxy,
+                PbrBundle {
+                    mesh: mesh.clone(),
+                    // Texture and frame are copied from the sprite in `update_cutout_shadows`
+                    material: materials.add(StandardMaterial {
+                        alpha_mode: AlphaMode::Mask(CUTOUT_ALPHA_THRESHOLD),
+                        unlit: true,
+                        cull_mode: None,
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                },
+                NotShadowReceiver,
+                RenderLayers::layer(SHADOW_PROXY_LAYER),
+            ))
+            .id();
+        commands.entity(caster).add_child(proxy);
+    }
+}
+
+pub(super) fn update_cutout_shadows(
+    light_query: Query<(&DirectionalLight, &GlobalTransform)>,
+    proxy_query: Query<(Entity, &Parent, &Handle<StandardMaterial>), With<ShadowProxy>>,
+    caster_material_query: Query<&Handle<StandardMaterial>, Without<ShadowProxy>>,
+    mut transform_query: Query<(&mut Transform, Option<&Parent>)>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let Some((_, light_transform)) = light_query.iter().find(|(light, _)| light.shadows_enabled)
+    else {
+        return;
+    };
+    let towards_light = light_transform.back().with_y(0.0);
+
+    for (proxy, parent, proxy_material) in proxy_query.iter() {
+        // Stand upright facing the light, so the shadow meets the sprite at its feet
+        if let Ok(towards_light) = Dir3::new(towards_light) {
+            let rotation = Transform::IDENTITY
+                .looking_to(towards_light, Vec3::Y)
+                .rotation;
+            let parent_rotation = world_transform(parent.get(), &transform_query).rotation;
+            if let Ok((mut transform, _)) = transform_query.get_mut(proxy) {
+                transform.rotation = parent_rotation.inverse() * rotation;
+            }
+        }
+
+        let Some((texture, uv_transform)) = caster_material_query
+            .get(parent.get())
+            .ok()
+            .and_then(|material| materials.get(material))
+            .map(|material| (material.base_color_texture.clone(), material.uv_transform))
+        else {
+            continue;
+        };
+        if materials.get(proxy_material).is_some_and(|material| {
+            material.base_color_texture != texture || material.uv_transform != uv_transform
+        }) {
+            if let Some(material) = materials.get_mut(proxy_material) {
+                material.base_color_texture = texture;
+                material.uv_transform = uv_transform;
+            }
+        }
+    }
+}
@@ -0,0 +1,148 @@
+use std::f32::consts::PI;
+
+use bevy::{
+    ecs::system::SystemParam,
+    pbr::NotShadowCaster,
+    prelude::*,
+    render::mesh::PlaneMeshBuilder,
+    sprite::Anchor,
+    utils::{HashMap, HashSet},
+};
+
+use crate::Billboard;
+
+/// Size and anchor of a textured quad drawn as a billboard.
+#[derive(Debug, Clone)]
+pub struct BillboardSprite {
+    pub texture: Handle<Image>,
+    pub size: Vec2,
+    /// Point of the quad placed at the entity's origin, as seen from the camera.
+    pub anchor: Anchor,
+}
+
+impl BillboardSprite {
+    pub fn new(texture: Handle<Image>) -> Self {
+        Self {
+            texture,
+            size: Vec2::ONE,
+            anchor: Anchor::Center,
+        }
+    }
+
+    pub fn with_size(mut self, size: Vec2) -> Self {
+        self.size = size;
+        self
+    }
+
+    pub fn with_anchor(mut self, anchor: Anchor) -> Self {
+        self.anchor = anchor;
+        self
+    }
+}
+
+#[derive(Bundle, Default)]
+pub struct BillboardSpriteBundle {
+    pub billboard: Billboard,
+    pub pbr: PbrBundle,
+    pub not_shadow_caster: NotShadowCaster,
+}
+
+impl BillboardSpriteBundle {
+    pub fn with_billboard(mut self, billboard: Billboard) -> Self {
+        self.billboard = billboard;
+        self
+    }
+
+    pub fn with_transform(mut self, transform: Transform) -> Self {
+        self.pbr.transform = transform;
+        self
+    }
+}
+
+/// Quads and materials shared between billboard sprites.
+#[derive(Resource, Debug, Default)]
+pub struct BillboardSpriteCache {
+    /// Keyed by the bits of the size and anchor point.
+    meshes: HashMap<[u32; 4], Handle<Mesh>>,
+    materials: HashMap<AssetId<Image>, Handle<StandardMaterial>>,
+    shared_materials: HashSet<AssetId<StandardMaterial>>,
+}
+
+#[derive(SystemParam)]
+pub struct BillboardSprites<'w> {
+    cache: ResMut<'w, BillboardSpriteCache>,
+    meshes: ResMut<'w, Assets<Mesh>>,
+    materials: ResMut<'w, Assets<StandardMaterial>>,
+}
+
+impl BillboardSprites<'_> {
+    pub fn bundle(&mut self, sprite: &BillboardSprite) -> BillboardSpriteBundle {
+        BillboardSpriteBundle {
+            pbr: PbrBundle {
+                mesh: self.shared_mesh(sprite.size, &sprite.anchor),
+                material: self.shared_material(&sprite.texture),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn shared_mesh(&mut self, size: Vec2, anchor: &Anchor) -> Handle<Mesh> {
+        let anchor = anchor.as_vec();
+        let key = [size.x, size.y, anchor.x, anchor.y].map(f32::to_bits);
+        let meshes = &mut self.meshes;
+        self.cache
+            .meshes
+            .entry(key)
+            .or_insert_with(|| {
+                // The billboard's local X axis points to the left of the screen
+                let offset = Vec3::new(anchor.x * size.x, -anchor.y * size.y, 0.0);
+                meshes.add(
+                    PlaneMeshBuilder::new(Dir3::NEG_Z, size)
+                        .build()
+                        .rotated_by(Quat::from_axis_angle(Vec3::NEG_Z, PI))
+                        .translated_by(offset),
+                )
+            })
+            .clone()
+    }
+
+    fn shared_material(&mut self, texture: &Handle<Image>) -> Handle<StandardMaterial> {
+        let materials = &mut self.materials;
+        let cache = &mut *self.cache;
+        let shared_materials = &mut cache.shared_materials;
+        cache
+            .materials
+            .entry(texture.id())
+            .or_insert_with(|| {
+                let material = materials.add(StandardMaterial {
+                    base_color_texture: Some(texture.clone()),
+                    alpha_mode: AlphaMode::Blend,
+                    unlit: true,
+                    // Mirroring a sprite turns its back to the camera
+                    cull_mode: None,
+                    ..Default::default()
+                });
+                shared_materials.insert(material.id());
+                material
+            })
+            .clone()
+    }
+
+    /// The material behind `handle` for editing, first giving the entity its own copy if the
+    /// material is shared with other sprites.
+    pub fn material_mut(
+        &mut self,
+        handle: &mut Handle<StandardMaterial>,
+    ) -> Option<&mut StandardMaterial> {
+        if self.cache.shared_materials.contains(&handle.id()) {
+            let material = self.materials.get(handle.id())?.clone();
+            *handle = self.materials.add(material);
+        }
+        self.materials.get_mut(handle.id())
+    }
+
+    pub fn material(&self, handle: &Handle<StandardMaterial>) -> Option<&StandardMaterial> {
+        self.materials.get(handle)
+    }
+}
@@ -0,0 +1,293 @@
+//! Checks `dialog.yarn` against the game: it has to compile, every node the level's NPCs and signs
+//! start has to exist, every command it runs has to be registered and every quest it starts has to
+//! be defined. Exits with a failure after listing every problem found.
+
+use std::{process::ExitCode, time::Duration};
+
+use bevy::{log::LogPlugin, prelude::*, utils::HashSet};
+use bevy_yarnspinner::prelude::YarnProject;
+use ludum_dare_56::{
+    headless::{wait_for_game_assets, HeadlessPlugins},
+    AppState, GamePlugin, Npc, NpcSchedule, Quests, Sign, WorldLoaded, YarnCommandRegistry,
+};
+use yarnspinner::prelude::{YarnCompiler, YarnFile};
+
+const DIALOG_PATH: &str = concat!(env!("CARGO_MANIFEST_DIR"), "/assets/dialog/dialog.yarn");
+/// Longest the level and the Yarn project may take to load.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(60);
+/// Frames to wait for the level to be spawned once its assets have loaded.
+const LOAD_FRAMES: usize = 60;
+/// Statements that look like commands but are part of the Yarn language.
+const YARN_KEYWORDS: &[&str] = &[
+    "if", "elseif", "else", "endif", "set", "call", "declare", "jump", "enum", "case", "endenum",
+    "local", "stop",
+];
+
+/// A `<<command>>` in the source, with the line it is on.
+struct CommandUse {
+    line: usize,
+    text: String,
+}
+
+impl CommandUse {
+    fn name(&self) -> &str {
+        self.text.split_whitespace().next().unwrap_or_default()
+    }
+
+    fn parameter(&self, index: usize) -> Option<&str> {
+        self.text.split_whitespace().nth(index + 1)
+    }
+}
+
+/// What the game registers and spawns, read from a headless game once its level has loaded.
+struct GameData {
+    /// Nodes started by the level's NPCs and signs, with what starts them.
+    started_nodes: Vec<(String, String)>,
+    quests: HashSet<&'static str>,
+    commands: HashSet<String>,
+}
+
+fn main() -> ExitCode {
+    let source = match std::fs::read_to_string(DIALOG_PATH) {
+        Ok(source) => source,
+        Err(error) => {
+            eprintln!("error: couldn't read {}: {}", DIALOG_PATH, error);
+            return ExitCode::FAILURE;
+        }
+    };
+    let compilation = match YarnCompiler::new()
+        .add_file(YarnFile {
+            file_name: "dialog.yarn".to_string(),
+            source: source.clone(),
+        })
+        .compile()
+    {
+        Ok(compilation) => compilation,
+        Err(error) => {
+            eprint!("{}", error);
+            eprintln!("error: dialog.yarn doesn't compile");
+            return ExitCode::FAILURE;
+        }
+    };
+    let nodes: HashSet<String> = compilation
+        .program
+        .map(|program| program.nodes.into_keys().collect())
+        .unwrap_or_default();
+
+    let Some(game) = load_game() else {
+        eprintln!(
+            "error: the level or Yarn project didn't load within {} seconds",
+            LOAD_TIMEOUT.as_secs()
+        );
+        return ExitCode::FAILURE;
+    };
+
+    let mut problems = Vec::new();
+    for (source, node) in game.started_nodes.iter() {
+        if !nodes.contains(node) {
+            problems.push(format!(
+                "{} starts node {:?}, which dialog.yarn doesn't have",
+                source, node
+            ));
+        }
+    }
+    let commands = find_commands(&source);
+    for command in commands.iter() {
+        if !game.commands.contains(command.name()) {
+            problems.push(format!(
+                "dialog.yarn:{}: <<{}>> isn't a registered command",
+                command.line, command.text
+            ));
+        } else if command.name() == "start_quest" {
+            // Quests given as {expressions} can only be checked at runtime
+            match command.parameter(0) {
+                Some(quest) if quest.contains('{') => (),
+                Some(quest) if !game.quests.contains(quest) => problems.push(format!(
+                    "dialog.yarn:{}: <<{}>> starts quest {:?}, which isn't defined",
+                    command.line, command.text, quest
+                )),
+                _ => (),
+            }
+        }
+    }
+
+    for problem in problems.iter() {
+        eprintln!("error: {}", problem);
+    }
+    if problems.is_empty() {
+        println!(
+            "dialog.yarn is fine: {} nodes, {} commands, {} nodes started by the level",
+            nodes.len(),
+            commands.len(),
+            game.started_nodes.len()
+        );
+        ExitCode::SUCCESS
+    } else {
+        eprintln!("{} problem(s) in dialog.yarn", problems.len());
+        ExitCode::FAILURE
+    }
+}
+
+/// Every `<<...>>` in `source` that isn't a Yarn statement, skipping comments.
+fn find_commands(source: &str) -> Vec<CommandUse> {
+    let mut commands = Vec::new();
+    for (index, line) in source.lines().enumerate() {
+        let mut rest = strip_comment(line);
+        while let Some((_, after_start)) = rest.split_once("<<") {
+            let Some((text, after_end)) = after_start.split_once(">>") else {
+                break;
+            };
+            let command = CommandUse {
+                line: index + 1,
+                text: text.trim().to_string(),
+            };
+            if !YARN_KEYWORDS.contains(&command.name()) {
+                commands.push(command);
+            }
+            rest = after_end;
+        }
+    }
+    commands
+}
+
+/// The part of `line` before its `//` comment. A `//` inside a command or straight after other
+/// text, as in a URL, doesn't start one.
+fn strip_comment(line: &str) -> &str {
+    let mut in_command = false;
+    let mut previous = None;
+    for (index, character) in line.char_indices() {
+        let rest = &line[index..];
+        if rest.starts_with("<<") {
+            in_command = true;
+        } else if rest.starts_with(">>") {
+            in_command = false;
+        } else if !in_command && rest.starts_with("//") && previous.is_none_or(char::is_whitespace)
+        {
+            return &line[..index];
+        }
+        previous = Some(character);
+    }
+    line
+}
+
+/// Runs the game headless until the level has been spawned, then collects what it registered and
+/// spawned.
+fn load_game() -> Option<GameData> {
+    let mut app = App::new();
+    app.add_plugins((HeadlessPlugins.build().disable::<LogPlugin>(), GamePlugin));
+    app.finish();
+    app.cleanup();
+    app.update();
+    app.world_mut()
+        .resource_mut::<NextState<AppState>>()
+        .set(AppState::InGame);
+
+    if !wait_for_game_assets(&mut app, LOAD_TIMEOUT) {
+        return None;
+    }
+    for _ in 0..LOAD_FRAMES {
+        if app.world().resource::<WorldLoaded>().0 {
+            break;
+        }
+        app.update();
+    }
+    if !app.world().resource::<WorldLoaded>().0 {
+        return None;
+    }
+    // Let the spawners handle the level's nodes
+    app.update();
+
+    let world = app.world_mut();
+    let mut started_nodes = Vec::new();
+    for (name, transform, npc) in world.query::<(&Name, &GlobalTransform, &Npc)>().iter(world) {
+        started_nodes.push((
+            format!("NPC {} at {:.1}", name, transform.translation()),
+            npc.0.clone(),
+        ));
+    }
+    for (name, schedule) in world.query::<(&Name, &NpcSchedule)>().iter(world) {
+        for entry in schedule.entries.iter() {
+            if let Some(node) = &entry.node {
+                started_nodes.push((
+                    format!("NPC {}'s schedule entry at {}", name, entry.location),
+                    node.clone(),
+                ));
+            }
+        }
+    }
+    for (name, sign) in world.query::<(&Name, &Sign)>().iter(world) {
+        if let Sign::Node(node) = sign {
+            started_nodes.push((format!("Sign {}", name), node.clone()));
+        }
+    }
+
+    let quests = world.resource::<Quests>().keys().copied().collect();
+    let mut commands: HashSet<String> = world
+        .resource::<YarnCommandRegistry>()
+        .names()
+        .map(str::to_string)
+        .collect();
+    // Built-in commands like <<wait>> are registered with each dialogue runner
+    commands.extend(
+        world
+            .resource::<YarnProject>()
+            .create_dialogue_runner()
+            .commands()
+            .names()
+            .map(str::to_string),
+    );
+    Some(GameData {
+        started_nodes,
+        quests,
+        commands,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn found(source: &str) -> Vec<(usize, String)> {
+        find_commands(source)
+            .into_iter()
+            .map(|command| (command.line, command.text))
+            .collect()
+    }
+
+    #[test]
+    fn skips_yarn_keywords() {
+        let source =
+            "<<if $met_ant>>\n<<set $met_ant to true>>\n<<start_quest ant_quest>>\n<<endif>>";
+        assert_eq!(found(source), [(3, "start_quest ant_quest".to_string())]);
+    }
+
+    #[test]
+    fn finds_commands_after_inline_if() {
+        let source = "Ant: Lead the way! <<if $following>> <<move_npc Ant Player>>";
+        assert_eq!(found(source), [(1, "move_npc Ant Player".to_string())]);
+    }
+
+    #[test]
+    fn finds_every_command_on_a_line() {
+        let source = "<<give_item seed 2>> <<take_item pebble 1>>";
+        assert_eq!(
+            found(source),
+            [
+                (1, "give_item seed 2".to_string()),
+                (1, "take_item pebble 1".to_string())
+            ]
+        );
+    }
+
+    #[test]
+    fn skips_comments_but_not_urls() {
+        let source = "// <<commented_out>>\n<<wait 1>> // <<also_commented>>\nSign: See https://example.com <<give_item seed 1>>";
+        assert_eq!(
+            found(source),
+            [
+                (2, "wait 1".to_string()),
+                (3, "give_item seed 1".to_string())
+            ]
+        );
+    }
+}
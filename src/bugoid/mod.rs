@@ -4,11 +4,10 @@ pub struct BugoidPlugin;
 
 impl Plugin for BugoidPlugin {
     fn build(&self, app: &mut App) {
-        
+        app.register_type::<Bugoid>();
     }
 }
 
-#[derive(Component, Debug)]
+#[derive(Component, Debug, Default, Reflect)]
+#[reflect(Component)]
 pub struct Bugoid;
-
-
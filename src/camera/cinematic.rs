@@ -0,0 +1,130 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::{
+    events::DialogueCompleteEvent,
+    prelude::{DialogueRunner, YarnValue},
+};
+
+use crate::{GameCamera, Player, PlayerState, YarnParameter};
+
+/// Exponential decay rate for blending between the gameplay orbit and a dialog shot.
+pub const SHOT_BLEND_DAMPING: f32 = 4.0;
+
+/// Framing used while the player is talking, switched from Yarn with `<<set_camera shot>>`.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum CameraShot {
+    /// Side-on view of the player and the speaker.
+    #[default]
+    TwoShot,
+    /// From behind the player towards the speaker.
+    OverShoulder,
+    /// Close on the speaker.
+    CloseUp,
+    /// Close on the player.
+    Reaction,
+}
+
+impl YarnParameter for CameraShot {
+    const TYPE_NAME: &'static str = "camera shot";
+
+    fn from_yarn_value(value: &YarnValue) -> Option<Self> {
+        match String::from(value.clone()).as_str() {
+            "two_shot" => Some(Self::TwoShot),
+            "over_shoulder" => Some(Self::OverShoulder),
+            "close_up" => Some(Self::CloseUp),
+            "reaction" => Some(Self::Reaction),
+            _ => None,
+        }
+    }
+}
+
+impl CameraShot {
+    /// Camera transform for this shot of a conversation between `player` and `speaker`.
+    fn transform(&self, player: Vec3, speaker: Vec3) -> Transform {
+        let towards_speaker = (speaker - player).with_y(0.0).normalize_or(Vec3::NEG_Z);
+        let side = towards_speaker.cross(Vec3::Y);
+        let (translation, target) = match self {
+            Self::TwoShot => {
+                let midpoint = player.midpoint(speaker);
+                let distance = player.distance(speaker) * 0.8 + 2.0;
+                (midpoint + side * distance + Vec3::Y, midpoint)
+            }
+            Self::OverShoulder => (
+                player - towards_speaker * 2.5 + side * 0.8 + Vec3::Y * 1.2,
+                speaker,
+            ),
+            Self::CloseUp => (speaker - towards_speaker * 2.5 + Vec3::Y * 0.3, speaker),
+            Self::Reaction => (player + towards_speaker * 2.5 + Vec3::Y * 0.3, player),
+        };
+        Transform::from_translation(translation).looking_at(target, Vec3::Y)
+    }
+}
+
+/// Marks a dialogue runner with the entity that is speaking, so the camera can frame it.
+#[derive(Component, Debug)]
+pub struct DialogueSpeaker(pub Entity);
+
+#[derive(Resource, Debug, Default)]
+pub struct DialogCamera {
+    pub shot: CameraShot,
+    /// How far the camera has blended from the gameplay orbit into the shot.
+    weight: f32,
+    /// Kept after the dialogue ends so the camera can blend back out of it.
+    last_shot_transform: Option<Transform>,
+}
+
+pub(super) fn reset_dialog_camera_shot(
+    mut end_event_reader: EventReader<DialogueCompleteEvent>,
+    mut dialog_camera: ResMut<DialogCamera>,
+) {
+    for _ in end_event_reader.read() {
+        dialog_camera.shot = CameraShot::default();
+    }
+}
+
+// <<set_camera shot>>
+pub(super) fn set_camera_command(
+    In(shot): In<CameraShot>,
+    mut dialog_camera: ResMut<DialogCamera>,
+) {
+    dialog_camera.shot = shot;
+}
+
+pub(super) fn apply_dialog_camera(
+    state: Res<State<PlayerState>>,
+    mut dialog_camera: ResMut<DialogCamera>,
+    speaker_query: Query<&DialogueSpeaker, With<DialogueRunner>>,
+    player_query: Query<&GlobalTransform, With<Player>>,
+    transform_query: Query<&GlobalTransform>,
+    mut camera_query: Query<&mut Transform, With<GameCamera>>,
+    time: Res<Time>,
+) {
+    let shot_transform = speaker_query
+        .get_single()
+        .ok()
+        .filter(|_| *state.get() == PlayerState::Dialog)
+        .and_then(|DialogueSpeaker(speaker)| transform_query.get(*speaker).ok())
+        .map(|speaker_transform| {
+            let player_transform = player_query.single();
+            dialog_camera.shot.transform(
+                player_transform.translation(),
+                speaker_transform.translation(),
+            )
+        });
+
+    let target_weight = if shot_transform.is_some() { 1.0 } else { 0.0 };
+    let blend = 1.0 - f32::exp(-SHOT_BLEND_DAMPING * time.delta_seconds());
+    dialog_camera.weight = dialog_camera.weight.lerp(target_weight, blend);
+    if shot_transform.is_some() {
+        dialog_camera.last_shot_transform = shot_transform;
+    }
+
+    if let Some(shot_transform) = dialog_camera.last_shot_transform {
+        let mut camera_transform = camera_query.single_mut();
+        camera_transform.translation = camera_transform
+            .translation
+            .lerp(shot_transform.translation, dialog_camera.weight);
+        camera_transform.rotation = camera_transform
+            .rotation
+            .slerp(shot_transform.rotation, dialog_camera.weight);
+    }
+}
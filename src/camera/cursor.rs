@@ -0,0 +1,59 @@
+use bevy::{
+    prelude::*,
+    window::{CursorGrabMode, PrimaryWindow},
+};
+use bevy_egui::EguiContext;
+
+use crate::{AppState, InventoryPanelState, PausedState, PlayerState};
+
+/// Locking the cursor in place is only supported on macOS and the web, everywhere else it is
+/// confined to the window instead.
+const CURSOR_GRAB_MODE: CursorGrabMode = if cfg!(any(target_os = "macos", target_arch = "wasm32")) {
+    CursorGrabMode::Locked
+} else {
+    CursorGrabMode::Confined
+};
+
+/// Set by overlays that need the mouse while the game keeps running, such as the dev tools.
+#[derive(Resource, Debug, Default)]
+pub struct CursorRelease(pub bool);
+
+/// Hides and grabs the cursor while the player is free to move around, so mouse movement only
+/// drives the camera then. Typing into an egui text field also releases it.
+pub(super) fn update_cursor_grab(
+    app_state: Res<State<AppState>>,
+    paused_state: Res<State<PausedState>>,
+    player_state: Res<State<PlayerState>>,
+    inventory_panel_state: Option<Res<InventoryPanelState>>,
+    cursor_release: Res<CursorRelease>,
+    mut egui_query: Query<&mut EguiContext, With<PrimaryWindow>>,
+    mut window_query: Query<&mut Window, With<PrimaryWindow>>,
+) {
+    let Ok(mut window) = window_query.get_single_mut() else {
+        return;
+    };
+    let captured = window.focused
+        && *app_state.get() == AppState::InGame
+        && *paused_state.get() == PausedState::Running
+        && *player_state.get() == PlayerState::Free
+        && !inventory_panel_state.is_some_and(|state| state.open)
+        && !cursor_release.0
+        && !egui_query
+            .get_single_mut()
+            .is_ok_and(|mut egui| egui.get_mut().wants_keyboard_input());
+    let grab_mode = if captured {
+        CURSOR_GRAB_MODE
+    } else {
+        CursorGrabMode::None
+    };
+    if window.cursor.grab_mode != grab_mode {
+        window.cursor.grab_mode = grab_mode;
+        window.cursor.visible = !captured;
+    }
+}
+
+pub fn cursor_captured(window_query: Query<&Window, With<PrimaryWindow>>) -> bool {
+    window_query
+        .get_single()
+        .is_ok_and(|window| window.cursor.grab_mode != CursorGrabMode::None)
+}
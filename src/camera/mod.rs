@@ -0,0 +1,250 @@
+use std::f32::consts::PI;
+
+use avian3d::{
+    collision::Collider,
+    spatial_query::{SpatialQuery, SpatialQueryFilter},
+};
+use bevy::{prelude::*, window::PrimaryWindow};
+use bevy_egui::EguiContext;
+use leafwing_input_manager::{
+    action_state::ActionState,
+    input_map::InputMap,
+    input_processing::{AxisProcessor, WithAxisProcessingPipelineExt},
+    plugin::InputManagerPlugin,
+    user_input::{KeyboardVirtualAxis, MouseMove, MouseScrollAxis},
+    Actionlike, InputControlKind, InputManagerBundle,
+};
+
+use crate::{
+    input_replaying, replay_camera, AppState, GameObject, GameplaySet, Player, PlayerState,
+    YarnRegistryAppExt,
+};
+
+mod cinematic;
+mod cursor;
+mod zones;
+pub use cinematic::*;
+pub use cursor::*;
+pub use zones::*;
+
+pub const DISTANCE_MIN: f32 = 3.0;
+pub const DISTANCE_MAX: f32 = 20.0;
+pub const PITCH_MIN: f32 = -PI / 12.0;
+pub const PITCH_MAX: f32 = 4.0 * PI / 9.0;
+pub const ZOOM_SPEED: f32 = 10.0;
+pub const ROTATE_SPEED: f32 = PI;
+/// Exponential decay rates; higher values catch up with the target faster.
+pub const FOLLOW_DAMPING: f32 = 10.0;
+pub const ROTATION_DAMPING: f32 = 15.0;
+pub const COLLISION_RELEASE_DAMPING: f32 = 3.0;
+/// Radius of the sphere cast towards the camera to keep the near plane out of walls.
+pub const COLLISION_RADIUS: f32 = 0.3;
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<CameraAction>::default())
+            .init_resource::<DialogCamera>()
+            .init_resource::<CameraZoneBlend>()
+            .init_resource::<CursorRelease>()
+            .add_systems(OnEnter(AppState::InGame), setup_camera)
+            .add_systems(Update, update_cursor_grab)
+            .add_systems(
+                Update,
+                (
+                    (
+                        apply_camera_controls.run_if(in_state(PlayerState::Free)),
+                        apply_mouse_look.run_if(cursor_captured),
+                    )
+                        .run_if(not(input_replaying)),
+                    replay_camera.run_if(input_replaying),
+                    camera_follow,
+                    apply_camera_zones,
+                    reset_dialog_camera_shot,
+                    apply_dialog_camera,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            )
+            .add_yarn_command("set_camera", set_camera_command);
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub enum CameraAction {
+    Zoom,
+    Rotate,
+    Pitch,
+    /// Mouse movement, which only turns the camera while the cursor is captured.
+    Look,
+}
+
+impl Actionlike for CameraAction {
+    fn input_control_kind(&self) -> InputControlKind {
+        match self {
+            Self::Zoom => InputControlKind::Axis,
+            Self::Rotate => InputControlKind::Axis,
+            Self::Pitch => InputControlKind::Axis,
+            Self::Look => InputControlKind::DualAxis,
+        }
+    }
+}
+
+/// Orbit around the player. `yaw`, `pitch` and `distance` are the values requested by input,
+/// which the camera eases towards.
+#[derive(Component, Debug)]
+pub struct GameCamera {
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+    current_yaw: f32,
+    current_pitch: f32,
+    current_distance: f32,
+    /// Distance actually used after pulling in the camera in front of occluding geometry.
+    unoccluded_distance: f32,
+    focus: Option<Vec3>,
+}
+
+impl GameCamera {
+    pub fn new(yaw: f32, pitch: f32, distance: f32) -> Self {
+        Self {
+            yaw,
+            pitch,
+            distance,
+            current_yaw: yaw,
+            current_pitch: pitch,
+            current_distance: distance,
+            unoccluded_distance: distance,
+            focus: None,
+        }
+    }
+
+    /// Rotation of the orbit as currently applied, before easing has caught up with input.
+    pub fn orbit_rotation(&self) -> Quat {
+        orbit_rotation(self.current_yaw, self.current_pitch)
+    }
+}
+
+/// Rotation that places a camera behind +Z at `yaw` around the up axis, raised by `pitch`.
+pub fn orbit_rotation(yaw: f32, pitch: f32) -> Quat {
+    Quat::from_euler(EulerRot::YXZ, yaw, -pitch, 0.0)
+}
+
+impl Default for GameCamera {
+    fn default() -> Self {
+        Self::new(0.0, PI / 6.0, 10.0)
+    }
+}
+
+fn setup_camera(mut commands: Commands) {
+    commands.spawn((
+        GameObject,
+        GameCamera::default(),
+        Camera3dBundle::default(),
+        InputManagerBundle::with_map(
+            InputMap::default()
+                .with_axis(CameraAction::Zoom, KeyboardVirtualAxis::VERTICAL_ARROW_KEYS)
+                .with_axis(
+                    CameraAction::Zoom,
+                    MouseScrollAxis::Y.with_processor(AxisProcessor::Sensitivity(5.0)),
+                )
+                .with_axis(
+                    CameraAction::Rotate,
+                    KeyboardVirtualAxis::HORIZONTAL_ARROW_KEYS,
+                )
+                .with_axis(
+                    CameraAction::Pitch,
+                    KeyboardVirtualAxis::new(KeyCode::KeyF, KeyCode::KeyR),
+                )
+                .with_dual_axis(CameraAction::Look, MouseMove::default()),
+        ),
+    ));
+}
+
+fn camera_follow(
+    player_query: Query<(Entity, &Transform), With<Player>>,
+    mut camera_query: Query<(&mut Transform, &mut GameCamera), Without<Player>>,
+    spatial_query: SpatialQuery,
+    time: Res<Time>,
+) {
+    let (player_entity, player_transform) = player_query.single();
+    let (mut camera_transform, mut camera) = camera_query.single_mut();
+    let delta = time.delta_seconds();
+    let follow_blend = 1.0 - f32::exp(-FOLLOW_DAMPING * delta);
+    let rotation_blend = 1.0 - f32::exp(-ROTATION_DAMPING * delta);
+
+    let focus = camera.focus.map_or(player_transform.translation, |focus| {
+        focus.lerp(player_transform.translation, follow_blend)
+    });
+    camera.focus = Some(focus);
+    camera.current_yaw = camera.current_yaw.lerp(camera.yaw, rotation_blend);
+    camera.current_pitch = camera.current_pitch.lerp(camera.pitch, rotation_blend);
+    camera.current_distance = camera.current_distance.lerp(camera.distance, follow_blend);
+
+    // Snap in front of anything between the player and the camera, then ease back out
+    let direction = Dir3::new_unchecked(camera.orbit_rotation() * Vec3::Z);
+    let clear_distance = spatial_query
+        .cast_shape(
+            &Collider::sphere(COLLISION_RADIUS),
+            focus,
+            Quat::IDENTITY,
+            direction,
+            camera.current_distance,
+            true,
+            SpatialQueryFilter::from_excluded_entities([player_entity]),
+        )
+        .map_or(camera.current_distance, |hit| hit.time_of_impact);
+    camera.unoccluded_distance = if clear_distance < camera.unoccluded_distance {
+        clear_distance
+    } else {
+        let release_blend = 1.0 - f32::exp(-COLLISION_RELEASE_DAMPING * delta);
+        camera
+            .unoccluded_distance
+            .lerp(clear_distance, release_blend)
+    };
+
+    camera_transform.translation = focus + direction * camera.unoccluded_distance;
+    camera_transform.look_at(focus, Vec3::Y);
+}
+
+/// Keyboard and scroll wheel controls, which work whether or not the cursor is captured.
+fn apply_camera_controls(
+    mut camera_query: Query<(&ActionState<CameraAction>, &mut GameCamera)>,
+    mut egui_query: Query<&mut EguiContext, With<PrimaryWindow>>,
+    time: Res<Time>,
+) {
+    if egui_query.get_single_mut().is_ok_and(|mut egui| {
+        let egui = egui.get_mut();
+        egui.wants_pointer_input() || egui.wants_keyboard_input()
+    }) {
+        return;
+    }
+    let (action_state, mut camera) = camera_query.single_mut();
+    let delta = time.delta_seconds();
+    turn_camera(
+        &mut camera,
+        action_state.value(&CameraAction::Rotate),
+        action_state.value(&CameraAction::Pitch),
+        delta,
+    );
+    if let Some(zoom_input) = action_state.axis_data(&CameraAction::Zoom) {
+        camera.distance = (camera.distance - zoom_input.value * ZOOM_SPEED * delta)
+            .clamp(DISTANCE_MIN, DISTANCE_MAX);
+    }
+}
+
+fn apply_mouse_look(
+    mut camera_query: Query<(&ActionState<CameraAction>, &mut GameCamera)>,
+    time: Res<Time>,
+) {
+    let (action_state, mut camera) = camera_query.single_mut();
+    let look_input = action_state.axis_pair(&CameraAction::Look);
+    let delta = time.delta_seconds();
+    turn_camera(&mut camera, look_input.x, look_input.y, delta);
+}
+
+fn turn_camera(camera: &mut GameCamera, yaw_input: f32, pitch_input: f32, delta: f32) {
+    camera.yaw -= yaw_input * ROTATE_SPEED * delta;
+    camera.pitch = (camera.pitch + pitch_input * ROTATE_SPEED * delta).clamp(PITCH_MIN, PITCH_MAX);
+}
@@ -0,0 +1,203 @@
+use bevy::{gltf::GltfExtras, prelude::*};
+use serde::Deserialize;
+
+use crate::{orbit_rotation, GameCamera, Player};
+
+/// A box-shaped volume placed from a `CameraZone_*` node that overrides the gameplay orbit while
+/// the player is inside it. The volume spans -1 to 1 on each axis of the node's transform, which
+/// matches a Blender cube empty.
+#[derive(Component, Debug, Clone, PartialEq, Deserialize)]
+pub struct CameraZone {
+    #[serde(flatten)]
+    pub mode: CameraZoneMode,
+    /// The highest priority zone containing the player wins.
+    #[serde(default)]
+    pub priority: i32,
+    /// Seconds taken to blend into this zone, and back out of it.
+    #[serde(default = "default_blend_time")]
+    pub blend_time: f32,
+}
+
+fn default_blend_time() -> f32 {
+    0.5
+}
+
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "camera_mode", rename_all = "snake_case")]
+pub enum CameraZoneMode {
+    /// Orbit at a fixed yaw and pitch (in degrees) and distance.
+    Fixed { yaw: f32, pitch: f32, distance: f32 },
+    /// Slide along the line between two nodes, following the point closest to the player.
+    Rail {
+        rail_start: String,
+        rail_end: String,
+    },
+    /// Keep the gameplay orbit position but look at a node instead of the player.
+    LookAt { look_at: String },
+}
+
+impl CameraZone {
+    pub fn from_extras(extras: &GltfExtras) -> Option<Self> {
+        serde_json::from_str(&extras.value).ok()
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub struct CameraZoneBlend {
+    active: Option<Entity>,
+    from: Option<Transform>,
+    progress: f32,
+    duration: f32,
+    last: Option<Transform>,
+}
+
+/// The highest priority zone whose volume contains `point`.
+fn active_zone<'a, T>(
+    zones: impl IntoIterator<Item = (T, &'a CameraZone, &'a GlobalTransform)>,
+    point: Vec3,
+) -> Option<(T, &'a CameraZone, &'a GlobalTransform)> {
+    zones
+        .into_iter()
+        .filter(|(_, _, zone_transform)| {
+            let local = zone_transform.affine().inverse().transform_point3(point);
+            local.abs().max_element() <= 1.0
+        })
+        .max_by_key(|(_, zone, _)| zone.priority)
+}
+
+fn closest_point_on_rail(start: Vec3, end: Vec3, point: Vec3) -> Vec3 {
+    let rail = end - start;
+    let along = (point - start).dot(rail) / rail.length_squared();
+    start + rail * along.clamp(0.0, 1.0)
+}
+
+pub(super) fn apply_camera_zones(
+    player_query: Query<&Transform, With<Player>>,
+    zone_query: Query<(Entity, &CameraZone, &GlobalTransform)>,
+    node_query: Query<(&Name, &GlobalTransform)>,
+    mut camera_query: Query<&mut Transform, (With<GameCamera>, Without<Player>)>,
+    mut blend: ResMut<CameraZoneBlend>,
+    time: Res<Time>,
+) {
+    let player_translation = player_query.single().translation;
+    let mut camera_transform = camera_query.single_mut();
+
+    let active = active_zone(zone_query.iter(), player_translation);
+
+    let active_entity = active.map(|(entity, _, _)| entity);
+    if active_entity != blend.active {
+        let blend_time = active
+            .map(|(_, zone, _)| zone)
+            .or_else(|| {
+                blend
+                    .active
+                    .and_then(|entity| zone_query.get(entity).ok())
+                    .map(|(_, zone, _)| zone)
+            })
+            .map_or(0.0, |zone| zone.blend_time);
+        blend.active = active_entity;
+        blend.from = blend.last;
+        blend.progress = 0.0;
+        blend.duration = blend_time;
+    }
+
+    let find_node = |name: &str| {
+        node_query
+            .iter()
+            .find(|(node_name, _)| node_name.as_str() == name)
+            .map(|(_, node_transform)| node_transform.translation())
+    };
+    let target = match active.map(|(_, zone, _)| &zone.mode) {
+        Some(CameraZoneMode::Fixed {
+            yaw,
+            pitch,
+            distance,
+        }) => Transform::from_translation(
+            player_translation
+                + orbit_rotation(yaw.to_radians(), pitch.to_radians()) * Vec3::Z * *distance,
+        )
+        .looking_at(player_translation, Vec3::Y),
+        Some(CameraZoneMode::Rail {
+            rail_start,
+            rail_end,
+        }) => match (find_node(rail_start), find_node(rail_end)) {
+            (Some(start), Some(end)) => {
+                Transform::from_translation(closest_point_on_rail(start, end, player_translation))
+                    .looking_at(player_translation, Vec3::Y)
+            }
+            _ => *camera_transform,
+        },
+        Some(CameraZoneMode::LookAt { look_at }) => match find_node(look_at) {
+            Some(look_at) => camera_transform.looking_at(look_at, Vec3::Y),
+            None => *camera_transform,
+        },
+        None => *camera_transform,
+    };
+
+    blend.progress = if blend.duration > 0.0 {
+        (blend.progress + time.delta_seconds() / blend.duration).min(1.0)
+    } else {
+        1.0
+    };
+    let weight = blend.progress * blend.progress * (3.0 - 2.0 * blend.progress);
+    *camera_transform = match blend.from {
+        Some(from) if weight < 1.0 => Transform {
+            translation: from.translation.lerp(target.translation, weight),
+            rotation: from.rotation.slerp(target.rotation, weight),
+            scale: target.scale,
+        },
+        _ => target,
+    };
+    blend.last = Some(*camera_transform);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn zone(priority: i32) -> CameraZone {
+        CameraZone {
+            mode: CameraZoneMode::LookAt {
+                look_at: "Anthill".to_string(),
+            },
+            priority,
+            blend_time: default_blend_time(),
+        }
+    }
+
+    #[test]
+    fn rail_follows_the_closest_point() {
+        let start = Vec3::new(0.0, 2.0, 0.0);
+        let end = Vec3::new(10.0, 2.0, 0.0);
+        assert_eq!(
+            closest_point_on_rail(start, end, Vec3::new(4.0, 0.0, 3.0)),
+            Vec3::new(4.0, 2.0, 0.0)
+        );
+        assert_eq!(
+            closest_point_on_rail(start, end, Vec3::new(-5.0, 0.0, 0.0)),
+            start
+        );
+        assert_eq!(
+            closest_point_on_rail(start, end, Vec3::new(15.0, 0.0, 0.0)),
+            end
+        );
+    }
+
+    #[test]
+    fn highest_priority_zone_containing_the_point_wins() {
+        let (low, high, elsewhere) = (zone(0), zone(5), zone(10));
+        let large = GlobalTransform::from_scale(Vec3::splat(10.0));
+        let small = GlobalTransform::from_translation(Vec3::new(2.0, 0.0, 0.0));
+        let far = GlobalTransform::from_translation(Vec3::new(50.0, 0.0, 0.0));
+        let zones = [
+            ("low", &low, &large),
+            ("high", &high, &small),
+            ("elsewhere", &elsewhere, &far),
+        ];
+
+        let active = |point| active_zone(zones, point).map(|(name, _, _)| name);
+        assert_eq!(active(Vec3::new(2.5, 0.5, 0.0)), Some("high"));
+        assert_eq!(active(Vec3::new(-5.0, 0.0, 0.0)), Some("low"));
+        assert_eq!(active(Vec3::new(20.0, 0.0, 0.0)), None);
+    }
+}
@@ -0,0 +1,303 @@
+use avian3d::prelude::{LinearVelocity, PhysicsGizmos};
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+use bevy_yarnspinner::prelude::{DialogueRunner, YarnProject};
+use leafwing_input_manager::{
+    action_state::ActionState, input_map::InputMap, plugin::InputManagerPlugin, Actionlike,
+    InputManagerBundle,
+};
+
+use crate::{
+    AppState, CameraAction, PausedState, Player, PlayerAction, QuestEvents, Quests,
+    YarnFunctionRegistry,
+};
+
+const CONSOLE_LOG_LINES: usize = 200;
+/// Height above a node the player is placed at by `teleport`, so they don't start in the ground.
+const TELEPORT_HEIGHT: f32 = 1.0;
+
+const HELP: &str = "\
+teleport <node>
+quest start|complete <id>
+yarn run <node>
+set_state main_menu|in_game|paused|running
+toggle physics_debug
+clear";
+
+#[derive(Actionlike, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub struct DevConsoleAction;
+
+#[derive(Component, Debug)]
+pub struct DevConsoleInput;
+
+#[derive(Resource, Debug, Default)]
+pub struct DevConsole {
+    pub open: bool,
+    input: String,
+    /// Previously run commands, oldest first.
+    history: Vec<String>,
+    /// Entry of `history` shown in the input while browsing it with the arrow keys.
+    history_index: Option<usize>,
+    log: Vec<String>,
+    /// Commands entered this frame, run with world access by `run_console_commands`.
+    pending: Vec<String>,
+}
+
+impl DevConsole {
+    fn print(&mut self, line: impl Into<String>) {
+        self.log.push(line.into());
+        if self.log.len() > CONSOLE_LOG_LINES {
+            self.log.remove(0);
+        }
+    }
+
+    fn browse_history(&mut self, older: bool) {
+        let index = match (self.history_index, older) {
+            (None, true) => self.history.len().checked_sub(1),
+            (None, false) => None,
+            (Some(index), true) => Some(index.saturating_sub(1)),
+            (Some(index), false) => Some(index + 1).filter(|index| *index < self.history.len()),
+        };
+        self.history_index = index;
+        self.input = index
+            .map(|index| self.history[index].clone())
+            .unwrap_or_default();
+    }
+}
+
+pub struct DevConsolePlugin;
+
+impl Plugin for DevConsolePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<DevConsoleAction>::default())
+            .init_resource::<DevConsole>()
+            .add_systems(Startup, setup_console_input)
+            .add_systems(
+                Update,
+                (
+                    process_console_input,
+                    block_game_input,
+                    show_console.run_if(|console: Res<DevConsole>| console.open),
+                    run_console_commands,
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn setup_console_input(mut commands: Commands) {
+    commands.spawn((
+        DevConsoleInput,
+        InputManagerBundle::with_map(InputMap::new([(DevConsoleAction, KeyCode::Backquote)])),
+    ));
+}
+
+fn process_console_input(
+    console_input_query: Query<&ActionState<DevConsoleAction>, With<DevConsoleInput>>,
+    mut console: ResMut<DevConsole>,
+) {
+    let action_state = console_input_query.single();
+    if action_state.just_pressed(&DevConsoleAction) {
+        console.open = !console.open;
+        // The text field sees the key that closes the console
+        console.input.retain(|c| c != '`');
+    }
+}
+
+/// Keeps typed commands from also moving the player and camera.
+fn block_game_input(
+    console: Res<DevConsole>,
+    mut player_query: Query<&mut ActionState<PlayerAction>>,
+    mut camera_query: Query<&mut ActionState<CameraAction>>,
+) {
+    for mut action_state in player_query.iter_mut() {
+        if console.open != action_state.disabled() {
+            match console.open {
+                true => action_state.disable(),
+                false => action_state.enable(),
+            }
+        }
+    }
+    for mut action_state in camera_query.iter_mut() {
+        if console.open != action_state.disabled() {
+            match console.open {
+                true => action_state.disable(),
+                false => action_state.enable(),
+            }
+        }
+    }
+}
+
+fn show_console(mut egui: EguiContexts, mut console: ResMut<DevConsole>) {
+    egui::Window::new("Console")
+        .anchor(Align2::CENTER_TOP, [0.0, 8.0])
+        .collapsible(false)
+        .resizable(false)
+        .default_width(600.0)
+        .show(egui.ctx_mut(), |ui| {
+            egui::ScrollArea::vertical()
+                .max_height(240.0)
+                .stick_to_bottom(true)
+                .show(ui, |ui| {
+                    for line in console.log.iter() {
+                        ui.monospace(line);
+                    }
+                });
+
+            let response = ui.add(
+                egui::TextEdit::singleline(&mut console.input)
+                    .desired_width(f32::INFINITY)
+                    .font(egui::TextStyle::Monospace),
+            );
+            if response.lost_focus() && ui.input(|input| input.key_pressed(egui::Key::Enter)) {
+                let line = std::mem::take(&mut console.input);
+                let line = line.trim();
+                if !line.is_empty() {
+                    console.history.push(line.to_string());
+                    console.pending.push(line.to_string());
+                }
+                console.history_index = None;
+            } else if response.has_focus() {
+                if ui.input(|input| input.key_pressed(egui::Key::ArrowUp)) {
+                    console.browse_history(true);
+                } else if ui.input(|input| input.key_pressed(egui::Key::ArrowDown)) {
+                    console.browse_history(false);
+                }
+            }
+            // Keep typing after running a command
+            response.request_focus();
+        });
+}
+
+fn run_console_commands(world: &mut World) {
+    let pending = std::mem::take(&mut world.resource_mut::<DevConsole>().pending);
+    for line in pending {
+        let output = run_console_command(world, &line);
+        let mut console = world.resource_mut::<DevConsole>();
+        console.print(format!("> {}", line));
+        match output {
+            Ok(output) if output.is_empty() => (),
+            Ok(output) => console.print(output),
+            Err(error) => console.print(format!("error: {}", error)),
+        }
+    }
+}
+
+fn run_console_command(world: &mut World, line: &str) -> Result<String, String> {
+    let words: Vec<_> = line.split_whitespace().collect();
+    match words.as_slice() {
+        ["help"] => Ok(HELP.to_string()),
+        ["clear"] => {
+            world.resource_mut::<DevConsole>().log.clear();
+            Ok(String::new())
+        }
+        ["teleport", node] => teleport(world, node),
+        ["quest", "start", id] => start_quest(world, id),
+        ["quest", "complete", id] => complete_quest(world, id),
+        ["yarn", "run", node] => run_yarn_node(world, node),
+        ["set_state", state] => set_state(world, state),
+        ["toggle", "physics_debug"] => {
+            let mut config_store = world.resource_mut::<GizmoConfigStore>();
+            let config = &mut config_store.config_mut::<PhysicsGizmos>().0;
+            config.enabled = !config.enabled;
+            Ok(format!("physics_debug {}", on_off(config.enabled)))
+        }
+        _ => Err(format!("unknown command {:?}, try help", line)),
+    }
+}
+
+fn teleport(world: &mut World, node: &str) -> Result<String, String> {
+    let target = world
+        .query::<(&Name, &GlobalTransform)>()
+        .iter(world)
+        .find(|(name, _)| name.as_str() == node)
+        .map(|(_, transform)| transform.translation())
+        .ok_or_else(|| format!("no node named {:?}", node))?;
+    let (mut transform, mut velocity) = world
+        .query_filtered::<(&mut Transform, &mut LinearVelocity), With<Player>>()
+        .get_single_mut(world)
+        .map_err(|_| "no player".to_string())?;
+    transform.translation = target + Vec3::Y * TELEPORT_HEIGHT;
+    velocity.0 = Vec3::ZERO;
+    Ok(format!("teleported to {}", target))
+}
+
+fn quest_name(world: &World, id: &str) -> Result<&'static str, String> {
+    world
+        .resource::<Quests>()
+        .get_key_value(id)
+        .map(|(name, _)| *name)
+        .ok_or_else(|| format!("unknown quest {:?}", id))
+}
+
+fn start_quest(world: &mut World, id: &str) -> Result<String, String> {
+    let name = quest_name(world, id)?;
+    world.send_event(QuestEvents::StartQuest(name));
+    Ok(format!("started {}", name))
+}
+
+/// Completes the quest whether or not its goal has been met, starting it first if needed.
+fn complete_quest(world: &mut World, id: &str) -> Result<String, String> {
+    let name = quest_name(world, id)?;
+    let mut quests = world.resource_mut::<Quests>();
+    let quest = quests.get_mut(name).unwrap();
+    let started = std::mem::replace(&mut quest.start, true);
+    quest.complete = true;
+    if !started {
+        world.send_event(QuestEvents::StartQuest(name));
+    }
+    world.send_event(QuestEvents::CompleteQuest(name));
+    Ok(format!("completed {}", name))
+}
+
+fn run_yarn_node(world: &mut World, node: &str) -> Result<String, String> {
+    if world
+        .query::<&DialogueRunner>()
+        .iter(world)
+        .next()
+        .is_some()
+    {
+        return Err("a dialogue is already running".to_string());
+    }
+    let project = world
+        .get_resource::<YarnProject>()
+        .ok_or_else(|| "the Yarn project hasn't loaded yet".to_string())?;
+    let mut dialog_runner = world
+        .resource::<YarnFunctionRegistry>()
+        .create_dialogue_runner(project);
+    dialog_runner
+        .try_start_node(node)
+        .map_err(|error| error.to_string())?;
+    world.spawn(dialog_runner);
+    Ok(format!("running {}", node))
+}
+
+fn set_state(world: &mut World, state: &str) -> Result<String, String> {
+    match state {
+        "main_menu" => world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::MainMenu),
+        "in_game" => world
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame),
+        "paused" => world
+            .resource_mut::<NextState<PausedState>>()
+            .set(PausedState::Paused),
+        "running" => world
+            .resource_mut::<NextState<PausedState>>()
+            .set(PausedState::Running),
+        _ => return Err(format!("unknown state {:?}", state)),
+    }
+    Ok(format!("state set to {}", state))
+}
+
+fn on_off(enabled: bool) -> &'static str {
+    if enabled {
+        "on"
+    } else {
+        "off"
+    }
+}
@@ -0,0 +1,87 @@
+use bevy::{prelude::*, utils::HashMap};
+use bevy_yarnspinner::prelude::DialogueRunner;
+
+use crate::{
+    spawn_world_scene, AppState, Inventory, ItemCatalog, LevelObject, Pickup, Player, WorldScene,
+};
+
+/// Respawns the level when `world.glb` or the item catalog change on disk. The Yarn project
+/// recompiles by itself once its files are watched.
+pub struct HotReloadPlugin;
+
+impl Plugin for HotReloadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, reload_level.run_if(in_state(AppState::InGame)))
+            // Before they are drawn
+            .add_systems(
+                PostUpdate,
+                remove_collected_pickups.run_if(in_state(AppState::InGame)),
+            );
+    }
+}
+
+/// Replaces the level scene and everything spawned for its nodes, so the spawners in
+/// `handle_world_load` run again on the new data. The player stays where they are.
+#[allow(clippy::too_many_arguments)]
+fn reload_level(
+    mut commands: Commands,
+    mut scene_events: EventReader<AssetEvent<Scene>>,
+    mut catalog_events: EventReader<AssetEvent<ItemCatalog>>,
+    scene_query: Query<(Entity, &Handle<Scene>), With<WorldScene>>,
+    level_object_query: Query<Entity, With<LevelObject>>,
+    dialogue_runner_query: Query<(), With<DialogueRunner>>,
+    asset_server: Res<AssetServer>,
+    mut reload_pending: Local<bool>,
+) {
+    let modified_scenes: Vec<_> = scene_events
+        .read()
+        .filter_map(|event| match event {
+            AssetEvent::Modified { id } => Some(*id),
+            _ => None,
+        })
+        .collect();
+    let catalog_modified = catalog_events
+        .read()
+        .any(|event| matches!(event, AssetEvent::Modified { .. }));
+    let Ok((scene_entity, scene)) = scene_query.get_single() else {
+        return;
+    };
+    if modified_scenes.contains(&scene.id()) || catalog_modified {
+        *reload_pending = true;
+    }
+    // The conversation may be with one of the level's NPCs, which the reload would despawn
+    if !*reload_pending || !dialogue_runner_query.is_empty() {
+        return;
+    }
+    *reload_pending = false;
+
+    info!("Reloading the level");
+    commands.entity(scene_entity).despawn_recursive();
+    for entity in level_object_query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+    spawn_world_scene(&mut commands, &asset_server);
+}
+
+/// Despawns respawned pickups for items the player already holds, so reloading the level doesn't
+/// hand them out again.
+#[allow(clippy::type_complexity)]
+fn remove_collected_pickups(
+    mut commands: Commands,
+    pickup_query: Query<(Entity, &Pickup), (Added<Pickup>, With<LevelObject>)>,
+    player_query: Query<&Inventory, With<Player>>,
+) {
+    let Ok(inventory) = player_query.get_single() else {
+        return;
+    };
+    let mut collected = HashMap::new();
+    for (entity, pickup) in pickup_query.iter() {
+        let remaining = collected
+            .entry(pickup.item.as_str())
+            .or_insert_with(|| inventory.count(&pickup.item));
+        if *remaining >= pickup.count {
+            *remaining -= pickup.count;
+            commands.entity(entity).despawn_recursive();
+        }
+    }
+}
@@ -0,0 +1,351 @@
+use bevy::{color::palettes::css, prelude::*};
+use bevy_egui::{egui, EguiContexts};
+use leafwing_input_manager::{
+    action_state::ActionState, input_map::InputMap, plugin::InputManagerPlugin, Actionlike,
+    InputManagerBundle,
+};
+
+use crate::{
+    Billboard, BillboardMode, Bugoid, CursorRelease, GameObject, Interactable, InteractionTarget,
+    Npc, QuestEvents, Quests,
+};
+
+/// Height above an interactable's origin its range is drawn at, to stay clear of the ground.
+const GIZMO_HEIGHT: f32 = 0.05;
+
+#[derive(Actionlike, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub struct InspectorAction;
+
+#[derive(Component, Debug)]
+pub struct InspectorInput;
+
+#[derive(Resource, Debug)]
+pub struct Inspector {
+    pub open: bool,
+    pub selected: Option<Entity>,
+    /// Draw the range of every interactable, in which `update_interaction_target` considers it.
+    pub show_interaction_ranges: bool,
+}
+
+impl Default for Inspector {
+    fn default() -> Self {
+        Self {
+            open: false,
+            selected: None,
+            show_interaction_ranges: true,
+        }
+    }
+}
+
+pub struct InspectorPlugin;
+
+impl Plugin for InspectorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<InspectorAction>::default())
+            .init_resource::<Inspector>()
+            .add_systems(Startup, setup_inspector_input)
+            .add_systems(
+                Update,
+                (
+                    process_inspector_input,
+                    (show_inspector, draw_interaction_ranges)
+                        .run_if(|inspector: Res<Inspector>| inspector.open),
+                )
+                    .chain(),
+            );
+    }
+}
+
+fn setup_inspector_input(mut commands: Commands) {
+    commands.spawn((
+        InspectorInput,
+        InputManagerBundle::with_map(InputMap::new([(InspectorAction, KeyCode::F1)])),
+    ));
+}
+
+fn process_inspector_input(
+    inspector_input_query: Query<&ActionState<InspectorAction>, With<InspectorInput>>,
+    mut inspector: ResMut<Inspector>,
+    mut cursor_release: ResMut<CursorRelease>,
+) {
+    let action_state = inspector_input_query.single();
+    if action_state.just_pressed(&InspectorAction) {
+        inspector.open = !inspector.open;
+        cursor_release.0 = inspector.open;
+    }
+}
+
+#[allow(clippy::type_complexity)]
+fn show_inspector(
+    mut egui: EguiContexts,
+    mut inspector: ResMut<Inspector>,
+    entity_query: Query<
+        (
+            Entity,
+            Option<&Name>,
+            Has<GameObject>,
+            Has<Npc>,
+            Has<Interactable>,
+            Has<Billboard>,
+            Has<Bugoid>,
+        ),
+        Or<(
+            With<GameObject>,
+            With<Npc>,
+            With<Interactable>,
+            With<Billboard>,
+            With<Bugoid>,
+        )>,
+    >,
+    mut component_query: Query<(
+        Option<&mut Transform>,
+        Option<&mut Npc>,
+        Option<&mut Interactable>,
+        Option<&mut Billboard>,
+    )>,
+    mut quests: ResMut<Quests>,
+    mut quest_events: EventWriter<QuestEvents>,
+) {
+    egui::SidePanel::left("inspector")
+        .default_width(300.0)
+        .show(egui.ctx_mut(), |ui| {
+            ui.heading("Inspector");
+            ui.checkbox(
+                &mut inspector.show_interaction_ranges,
+                "Show interaction ranges",
+            );
+
+            egui::ScrollArea::vertical()
+                .id_salt("entities")
+                .max_height(ui.available_height() / 2.0)
+                .show(ui, |ui| {
+                    let mut entities: Vec<_> = entity_query.iter().collect();
+                    entities.sort_by_key(|(entity, ..)| *entity);
+                    for (category, index) in [
+                        ("GameObject", 0),
+                        ("Npc", 1),
+                        ("Interactable", 2),
+                        ("Billboard", 3),
+                        ("Bugoid", 4),
+                    ] {
+                        egui::CollapsingHeader::new(category).show(ui, |ui| {
+                            for (entity, name, ..) in entities.iter().filter(
+                                |(_, _, game_object, npc, interactable, billboard, bugoid)| {
+                                    [*game_object, *npc, *interactable, *billboard, *bugoid][index]
+                                },
+                            ) {
+                                let label = match name {
+                                    Some(name) => format!("{} ({})", name, entity),
+                                    None => entity.to_string(),
+                                };
+                                if ui
+                                    .selectable_label(inspector.selected == Some(*entity), label)
+                                    .clicked()
+                                {
+                                    inspector.selected = Some(*entity);
+                                }
+                            }
+                        });
+                    }
+                });
+
+            ui.separator();
+            if let Some(entity) = inspector.selected {
+                if let Ok((transform, npc, interactable, billboard)) =
+                    component_query.get_mut(entity)
+                {
+                    ui.label(format!("Selected {}", entity));
+                    if let Some(transform) = transform {
+                        edit_transform(ui, transform);
+                    }
+                    if let Some(npc) = npc {
+                        edit_npc(ui, npc);
+                    }
+                    if let Some(interactable) = interactable {
+                        edit_interactable(ui, interactable);
+                    }
+                    if let Some(billboard) = billboard {
+                        edit_billboard(ui, billboard);
+                    }
+                } else {
+                    inspector.selected = None;
+                }
+            }
+
+            ui.separator();
+            edit_quests(ui, &mut quests, &mut quest_events);
+        });
+}
+
+fn edit_transform(ui: &mut egui::Ui, mut transform: Mut<Transform>) {
+    ui.collapsing("Transform", |ui| {
+        let mut translation = transform.translation;
+        let (yaw, pitch, roll) = transform.rotation.to_euler(EulerRot::YXZ);
+        let mut rotation = Vec3::new(pitch.to_degrees(), yaw.to_degrees(), roll.to_degrees());
+        let mut scale = transform.scale;
+        let changed = edit_vec3(ui, "Translation", &mut translation, 0.05)
+            | edit_vec3(ui, "Rotation", &mut rotation, 1.0)
+            | edit_vec3(ui, "Scale", &mut scale, 0.01);
+        // Only write back edits, so the entity isn't marked changed every frame
+        if changed {
+            transform.translation = translation;
+            transform.rotation = Quat::from_euler(
+                EulerRot::YXZ,
+                rotation.y.to_radians(),
+                rotation.x.to_radians(),
+                rotation.z.to_radians(),
+            );
+            transform.scale = scale;
+        }
+    });
+}
+
+fn edit_vec3(ui: &mut egui::Ui, label: &str, value: &mut Vec3, speed: f32) -> bool {
+    ui.horizontal(|ui| {
+        ui.label(label);
+        ui.add(egui::DragValue::new(&mut value.x).speed(speed))
+            .changed()
+            | ui.add(egui::DragValue::new(&mut value.y).speed(speed))
+                .changed()
+            | ui.add(egui::DragValue::new(&mut value.z).speed(speed))
+                .changed()
+    })
+    .inner
+}
+
+fn edit_npc(ui: &mut egui::Ui, mut npc: Mut<Npc>) {
+    ui.collapsing("Npc", |ui| {
+        let mut node = npc.0.clone();
+        ui.horizontal(|ui| {
+            ui.label("Yarn node");
+            if ui.text_edit_singleline(&mut node).changed() {
+                npc.0 = node;
+            }
+        });
+    });
+}
+
+fn edit_interactable(ui: &mut egui::Ui, mut interactable: Mut<Interactable>) {
+    ui.collapsing("Interactable", |ui| {
+        ui.label(format!("Kind: {:?}", interactable.kind));
+        let mut prompt = interactable.prompt.clone();
+        let mut range = interactable.range;
+        let mut max_angle = interactable.max_angle.to_degrees();
+        ui.horizontal(|ui| {
+            ui.label("Prompt");
+            if ui.text_edit_singleline(&mut prompt).changed() {
+                interactable.prompt = prompt;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Range");
+            if ui
+                .add(
+                    egui::DragValue::new(&mut range)
+                        .speed(0.05)
+                        .range(0.0..=10.0),
+                )
+                .changed()
+            {
+                interactable.range = range;
+            }
+        });
+        ui.horizontal(|ui| {
+            ui.label("Max angle");
+            if ui
+                .add(egui::DragValue::new(&mut max_angle).range(0.0..=180.0))
+                .changed()
+            {
+                interactable.max_angle = max_angle.to_radians();
+            }
+        });
+    });
+}
+
+fn edit_billboard(ui: &mut egui::Ui, mut billboard: Mut<Billboard>) {
+    ui.collapsing("Billboard", |ui| {
+        let mut mode = billboard.mode;
+        egui::ComboBox::from_label("Mode")
+            .selected_text(format!("{:?}", mode))
+            .show_ui(ui, |ui| {
+                for option in [
+                    BillboardMode::ScreenAligned,
+                    BillboardMode::Spherical,
+                    BillboardMode::Cylindrical,
+                    BillboardMode::None,
+                ] {
+                    ui.selectable_value(&mut mode, option, format!("{:?}", option));
+                }
+            });
+        if mode != billboard.mode {
+            billboard.mode = mode;
+        }
+        ui.label(format!("Directions: {:?}", billboard.directions));
+        ui.label(format!("Frame: {} of {}", billboard.row, billboard.rows));
+    });
+}
+
+/// Quest flags can only be set, since Yarn's copy of them never forgets a started quest.
+fn edit_quests(
+    ui: &mut egui::Ui,
+    quests: &mut Quests,
+    quest_events: &mut EventWriter<QuestEvents>,
+) {
+    ui.collapsing("Quests", |ui| {
+        let mut names: Vec<_> = quests.keys().copied().collect();
+        names.sort();
+        for name in names {
+            let quest = quests.get_mut(name).unwrap();
+            ui.horizontal(|ui| {
+                ui.label(name);
+                let (mut start, mut complete) = (quest.start, quest.complete);
+                if ui
+                    .add_enabled(!quest.start, egui::Checkbox::new(&mut start, "started"))
+                    .changed()
+                {
+                    quest_events.send(QuestEvents::StartQuest(name));
+                }
+                if ui
+                    .add_enabled(
+                        !quest.complete,
+                        egui::Checkbox::new(&mut complete, "complete"),
+                    )
+                    .changed()
+                {
+                    if !quest.start {
+                        quest.start = true;
+                        quest_events.send(QuestEvents::StartQuest(name));
+                    }
+                    quest.complete = true;
+                    quest_events.send(QuestEvents::CompleteQuest(name));
+                }
+            });
+        }
+    });
+}
+
+fn draw_interaction_ranges(
+    inspector: Res<Inspector>,
+    interactable_query: Query<(Entity, &GlobalTransform, &Interactable)>,
+    target: Res<InteractionTarget>,
+    mut gizmos: Gizmos,
+) {
+    if !inspector.show_interaction_ranges {
+        return;
+    }
+    for (entity, transform, interactable) in interactable_query.iter() {
+        let color = if **target == Some(entity) {
+            css::LIME
+        } else if inspector.selected == Some(entity) {
+            css::AQUA
+        } else {
+            css::YELLOW
+        };
+        gizmos.circle(
+            transform.translation() + Vec3::Y * GIZMO_HEIGHT,
+            Dir3::Y,
+            interactable.range,
+            color,
+        );
+    }
+}
@@ -0,0 +1,29 @@
+use avian3d::prelude::{PhysicsDebugPlugin, PhysicsGizmos};
+use bevy::prelude::*;
+
+mod console;
+mod hot_reload;
+mod inspector;
+pub use console::*;
+pub use hot_reload::*;
+pub use inspector::*;
+
+/// Debug tooling compiled in with the `dev` feature.
+pub struct DevPlugin;
+
+impl Plugin for DevPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            PhysicsDebugPlugin::default(),
+            DevConsolePlugin,
+            InspectorPlugin,
+            HotReloadPlugin,
+        ))
+        .add_systems(Startup, hide_physics_debug);
+    }
+}
+
+/// Physics gizmos start hidden, `toggle physics_debug` shows them.
+fn hide_physics_debug(mut config_store: ResMut<GizmoConfigStore>) {
+    config_store.config_mut::<PhysicsGizmos>().0.enabled = false;
+}
@@ -0,0 +1,88 @@
+use bevy::{asset::AssetLoadFailedEvent, prelude::*};
+use bevy_yarnspinner::{
+    deferred_loading::LoadYarnProjectEvent,
+    events::{DialogueCompleteEvent, DialogueStartEvent, PresentLineEvent},
+    prelude::{DialogueRunner, YarnFileSource, YarnSpinnerPlugin, YarnSpinnerSystemSet},
+};
+
+use crate::{dialog_box::DialogBoxContent, game_asset, GameObject, GameplaySet, PlayerState};
+
+mod registry;
+pub use registry::*;
+
+pub struct DialogPlugin;
+
+impl Plugin for DialogPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(YarnSpinnerPlugin::deferred())
+            .init_resource::<YarnCommandRegistry>()
+            .init_resource::<YarnFunctionRegistry>()
+            .init_resource::<DialogBoxContent>()
+            // Runners started by interactions run the same frame, and their events are handled the
+            // next one, so replays see dialogues advance on the same ticks
+            .configure_sets(Update, YarnSpinnerSystemSet.after(GameplaySet))
+            .add_systems(Startup, setup_yarnspinner)
+            .add_systems(
+                Update,
+                (handle_yarnspinner_events, dispatch_yarn_commands).in_set(GameplaySet),
+            )
+            .add_systems(Update, warn_failed_sounds)
+            .add_yarn_command("play_sound", play_sound_command);
+    }
+}
+
+fn setup_yarnspinner(
+    asset_server: Res<AssetServer>,
+    mut event_writer: EventWriter<LoadYarnProjectEvent>,
+) {
+    event_writer.send(LoadYarnProjectEvent::with_yarn_source(
+        YarnFileSource::Handle(asset_server.load(game_asset("dialog/dialog.yarn"))),
+    ));
+}
+
+fn handle_yarnspinner_events(
+    mut start_event_reader: EventReader<DialogueStartEvent>,
+    mut line_event_reader: EventReader<PresentLineEvent>,
+    mut end_event_reader: EventReader<DialogueCompleteEvent>,
+    mut next_player_state: ResMut<NextState<PlayerState>>,
+    mut dialog_box_content: ResMut<DialogBoxContent>,
+    mut commands: Commands,
+    runner_query: Query<Entity, With<DialogueRunner>>,
+) {
+    for _ in start_event_reader.read() {
+        next_player_state.set(PlayerState::Dialog);
+    }
+
+    for _ in end_event_reader.read() {
+        let runner_entity = runner_query.single();
+        commands.entity(runner_entity).despawn_recursive();
+        next_player_state.set(PlayerState::Free);
+    }
+
+    for PresentLineEvent { line, .. } in line_event_reader.read() {
+        dialog_box_content.character = line.character_name().map(str::to_string);
+        dialog_box_content.line = line.text_without_character_name();
+    }
+}
+
+// <<play_sound name>> plays `assets/sounds/<name>.ogg`. Release builds only find the sounds that
+// `embed_assets` lists, and there are none yet.
+fn play_sound_command(
+    In(name): In<String>,
+    asset_server: Res<AssetServer>,
+    mut commands: Commands,
+) {
+    commands.spawn((
+        GameObject,
+        AudioBundle {
+            source: asset_server.load(game_asset(&format!("sounds/{}.ogg", name))),
+            settings: PlaybackSettings::DESPAWN,
+        },
+    ));
+}
+
+fn warn_failed_sounds(mut event_reader: EventReader<AssetLoadFailedEvent<AudioSource>>) {
+    for event in event_reader.read() {
+        warn!("Couldn't play sound {}: {}", event.path, event.error);
+    }
+}
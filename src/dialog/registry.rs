@@ -0,0 +1,297 @@
+use std::fmt;
+
+use bevy::{ecs::system::SystemId, prelude::*, utils::HashMap};
+use bevy_yarnspinner::{
+    events::ExecuteCommandEvent,
+    prelude::{
+        DialogueRunner, IntoYarnValueFromNonYarnValue, YarnFn, YarnLibrary, YarnProject, YarnValue,
+    },
+};
+
+/// Yarn commands that any plugin can register with [`YarnRegistryAppExt::add_yarn_command`].
+#[derive(Resource, Default)]
+pub struct YarnCommandRegistry(HashMap<&'static str, Box<YarnCommandHandler>>);
+
+impl YarnCommandRegistry {
+    pub fn names(&self) -> impl Iterator<Item = &'static str> + '_ {
+        self.0.keys().copied()
+    }
+}
+
+type YarnCommandHandler =
+    dyn Fn(&mut Commands, &[YarnValue]) -> Result<(), YarnCommandError> + Send + Sync;
+
+/// Yarn functions that are added to the library of every new [`DialogueRunner`].
+#[derive(Resource, Default)]
+pub struct YarnFunctionRegistry(YarnLibrary);
+
+impl YarnFunctionRegistry {
+    /// Creates a dialogue runner for `project` with every registered function available.
+    pub fn create_dialogue_runner(&self, project: &YarnProject) -> DialogueRunner {
+        let mut dialogue_runner = project.create_dialogue_runner();
+        dialogue_runner.library_mut().extend(self.0.clone());
+        dialogue_runner
+    }
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum YarnCommandError {
+    Arity {
+        expected: usize,
+        found: usize,
+    },
+    Parameter {
+        index: usize,
+        value: String,
+        expected: &'static str,
+    },
+}
+
+impl fmt::Display for YarnCommandError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Arity { expected, found } => {
+                write!(f, "expected {expected} parameter(s) but got {found}")
+            }
+            Self::Parameter {
+                index,
+                value,
+                expected,
+            } => write!(f, "parameter {index} ({value:?}) is not a valid {expected}"),
+        }
+    }
+}
+
+/// A single value that can be parsed from a Yarn command parameter.
+pub trait YarnParameter: Sized + Send + 'static {
+    const TYPE_NAME: &'static str;
+
+    fn from_yarn_value(value: &YarnValue) -> Option<Self>;
+}
+
+impl YarnParameter for String {
+    const TYPE_NAME: &'static str = "string";
+
+    fn from_yarn_value(value: &YarnValue) -> Option<Self> {
+        Some(String::from(value.clone()))
+    }
+}
+
+impl YarnParameter for f32 {
+    const TYPE_NAME: &'static str = "number";
+
+    fn from_yarn_value(value: &YarnValue) -> Option<Self> {
+        f32::try_from(value).ok()
+    }
+}
+
+impl YarnParameter for bool {
+    const TYPE_NAME: &'static str = "boolean";
+
+    fn from_yarn_value(value: &YarnValue) -> Option<Self> {
+        bool::try_from(value).ok()
+    }
+}
+
+impl YarnParameter for u32 {
+    const TYPE_NAME: &'static str = "whole number";
+
+    fn from_yarn_value(value: &YarnValue) -> Option<Self> {
+        f32::try_from(value)
+            .ok()
+            .filter(|number| number.fract() == 0.0 && *number >= 0.0)
+            .map(|number| number as u32)
+    }
+}
+
+/// The full parameter list of a Yarn command, i.e. `()`, a single [`YarnParameter`] or a tuple of them.
+pub trait YarnParameters: Sized + Send + 'static {
+    const ARITY: usize;
+
+    fn parse(values: &[YarnValue]) -> Result<Self, YarnCommandError>;
+}
+
+fn parse_parameter<T: YarnParameter>(
+    values: &[YarnValue],
+    index: usize,
+) -> Result<T, YarnCommandError> {
+    T::from_yarn_value(&values[index]).ok_or_else(|| YarnCommandError::Parameter {
+        index,
+        value: values[index].to_string(),
+        expected: T::TYPE_NAME,
+    })
+}
+
+fn check_arity(values: &[YarnValue], expected: usize) -> Result<(), YarnCommandError> {
+    if values.len() == expected {
+        Ok(())
+    } else {
+        Err(YarnCommandError::Arity {
+            expected,
+            found: values.len(),
+        })
+    }
+}
+
+impl YarnParameters for () {
+    const ARITY: usize = 0;
+
+    fn parse(values: &[YarnValue]) -> Result<Self, YarnCommandError> {
+        check_arity(values, Self::ARITY)
+    }
+}
+
+impl<T: YarnParameter> YarnParameters for T {
+    const ARITY: usize = 1;
+
+    fn parse(values: &[YarnValue]) -> Result<Self, YarnCommandError> {
+        check_arity(values, Self::ARITY)?;
+        parse_parameter(values, 0)
+    }
+}
+
+macro_rules! impl_yarn_parameters {
+    ($arity:literal, $(($param:ident, $index:tt)),*) => {
+        impl<$($param: YarnParameter),*> YarnParameters for ($($param,)*) {
+            const ARITY: usize = $arity;
+
+            fn parse(values: &[YarnValue]) -> Result<Self, YarnCommandError> {
+                check_arity(values, Self::ARITY)?;
+                Ok(($(parse_parameter::<$param>(values, $index)?,)*))
+            }
+        }
+    };
+}
+
+impl_yarn_parameters!(2, (A, 0), (B, 1));
+impl_yarn_parameters!(3, (A, 0), (B, 1), (C, 2));
+impl_yarn_parameters!(4, (A, 0), (B, 1), (C, 2), (D, 3));
+
+pub trait YarnRegistryAppExt {
+    /// Registers a system that runs whenever `<<name ...>>` is executed by a dialogue runner. The
+    /// system's input is parsed from the command parameters.
+    fn add_yarn_command<P: YarnParameters, M>(
+        &mut self,
+        name: &'static str,
+        system: impl IntoSystem<P, (), M> + 'static,
+    ) -> &mut Self;
+
+    /// Registers a function callable from Yarn expressions, e.g. `<<if quest_complete("ant_quest")>>`.
+    fn add_yarn_function<M: 'static, F>(&mut self, name: &'static str, function: F) -> &mut Self
+    where
+        F: YarnFn<M> + 'static + Clone,
+        F::Out: IntoYarnValueFromNonYarnValue + 'static + Clone;
+}
+
+impl YarnRegistryAppExt for App {
+    fn add_yarn_command<P: YarnParameters, M>(
+        &mut self,
+        name: &'static str,
+        system: impl IntoSystem<P, (), M> + 'static,
+    ) -> &mut Self {
+        let system_id: SystemId<P> = self.world_mut().register_system(system);
+        let handler = move |commands: &mut Commands, values: &[YarnValue]| {
+            commands.run_system_with_input(system_id, P::parse(values)?);
+            Ok(())
+        };
+        self.world_mut()
+            .get_resource_or_insert_with(YarnCommandRegistry::default)
+            .0
+            .insert(name, Box::new(handler));
+        self
+    }
+
+    fn add_yarn_function<M: 'static, F>(&mut self, name: &'static str, function: F) -> &mut Self
+    where
+        F: YarnFn<M> + 'static + Clone,
+        F::Out: IntoYarnValueFromNonYarnValue + 'static + Clone,
+    {
+        self.world_mut()
+            .get_resource_or_insert_with(YarnFunctionRegistry::default)
+            .0
+            .add_function(name, function);
+        self
+    }
+}
+
+pub(super) fn dispatch_yarn_commands(
+    mut command_event_reader: EventReader<ExecuteCommandEvent>,
+    command_registry: Res<YarnCommandRegistry>,
+    runner_query: Query<&DialogueRunner>,
+    mut commands: Commands,
+) {
+    for ExecuteCommandEvent { command, source } in command_event_reader.read() {
+        let name = command.name.as_str();
+        if let Some(handler) = command_registry.0.get(name) {
+            if let Err(error) = handler(&mut commands, &command.parameters) {
+                error!("Invalid Yarn command <<{}>>: {}", command.raw, error);
+            }
+        } else if !runner_query
+            .get(*source)
+            .is_ok_and(|runner| runner.commands().contains_key(name))
+        {
+            warn!("Unknown Yarn command <<{}>>", command.raw);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn wrong_arity_is_rejected() {
+        assert_eq!(
+            check_arity(&[YarnValue::from("Ant")], 2),
+            Err(YarnCommandError::Arity {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            <(String, u32)>::parse(&[YarnValue::from("seed")]),
+            Err(YarnCommandError::Arity {
+                expected: 2,
+                found: 1
+            })
+        );
+        assert_eq!(
+            <() as YarnParameters>::parse(&[YarnValue::from(1.0)]),
+            Err(YarnCommandError::Arity {
+                expected: 0,
+                found: 1
+            })
+        );
+    }
+
+    #[test]
+    fn u32_rejects_non_numbers() {
+        assert_eq!(
+            <u32 as YarnParameters>::parse(&[YarnValue::from("seed")]),
+            Err(YarnCommandError::Parameter {
+                index: 0,
+                value: "seed".to_string(),
+                expected: "whole number"
+            })
+        );
+    }
+
+    #[test]
+    fn u32_rejects_fractional_and_negative_numbers() {
+        assert_eq!(u32::from_yarn_value(&YarnValue::from(1.5)), None);
+        assert_eq!(u32::from_yarn_value(&YarnValue::from(-2.0)), None);
+        assert_eq!(u32::from_yarn_value(&YarnValue::from(3.0)), Some(3));
+        assert!(matches!(
+            <(String, u32)>::parse(&[YarnValue::from("seed"), YarnValue::from(0.5)]),
+            Err(YarnCommandError::Parameter { index: 1, .. })
+        ));
+    }
+
+    #[test]
+    fn two_tuple_parses() {
+        assert_eq!(
+            <(String, u32)>::parse(&[YarnValue::from("seed"), YarnValue::from(2.0)]),
+            Ok(("seed".to_string(), 2))
+        );
+    }
+}
@@ -0,0 +1,44 @@
+use avian3d::prelude::LinearVelocity;
+use bevy::prelude::*;
+
+use crate::{GameplaySet, InteractEvent, InteractionKind, Player};
+
+pub struct DoorPlugin;
+
+impl Plugin for DoorPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_door_interactions.in_set(GameplaySet));
+    }
+}
+
+/// Moves the player to the level node named `destination` when used.
+#[derive(Component, Debug)]
+pub struct Door {
+    pub destination: String,
+}
+
+fn handle_door_interactions(
+    door_query: Query<&Door>,
+    destination_query: Query<(&Name, &GlobalTransform)>,
+    mut player_query: Query<(&mut Transform, &mut LinearVelocity), With<Player>>,
+    mut event_reader: EventReader<InteractEvent>,
+) {
+    for InteractEvent { entity, .. } in event_reader
+        .read()
+        .filter(|event| event.kind == InteractionKind::UseDoor)
+    {
+        let Ok(door) = door_query.get(*entity) else {
+            continue;
+        };
+        let Some((_, destination_transform)) = destination_query
+            .iter()
+            .find(|(name, _)| name.as_str() == door.destination)
+        else {
+            warn!("Door destination {:?} not found", door.destination);
+            continue;
+        };
+        let (mut player_transform, mut player_velocity) = player_query.single_mut();
+        player_transform.translation = destination_transform.translation() + Vec3::Y;
+        *player_velocity = LinearVelocity::ZERO;
+    }
+}
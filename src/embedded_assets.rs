@@ -0,0 +1,39 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{asset::io::embedded::EmbeddedAssetRegistry, prelude::*};
+
+/// Where the game loads `assets/` from: embedded in the binary, or with the `dev` feature straight
+/// from the folder, watched so edits show up while the game runs.
+#[cfg(not(feature = "dev"))]
+pub const ASSET_ROOT: &str = "embedded://ludum_dare_56/";
+#[cfg(feature = "dev")]
+pub const ASSET_ROOT: &str = "";
+
+/// Path of `path` within `assets/` that loads from [`ASSET_ROOT`].
+pub fn game_asset(path: &str) -> String {
+    format!("{}{}", ASSET_ROOT, path)
+}
+
+/// Embeds `assets/$path` as `embedded://ludum_dare_56/$path`.
+macro_rules! embed_asset {
+    ($app:ident, $path:literal) => {
+        $app.world_mut()
+            .resource_mut::<EmbeddedAssetRegistry>()
+            .insert_asset(
+                PathBuf::from(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $path)),
+                Path::new(concat!("ludum_dare_56/", $path)),
+                include_bytes!(concat!(env!("CARGO_MANIFEST_DIR"), "/assets/", $path)).as_slice(),
+            )
+    };
+}
+
+pub fn embed_assets(app: &mut App) {
+    embed_asset!(app, "models/world.glb");
+    embed_asset!(app, "dialog/dialog.yarn");
+    embed_asset!(app, "textures/pillbug.png");
+    embed_asset!(app, "textures/ant.png");
+    embed_asset!(app, "textures/seed.png");
+    embed_asset!(app, "items/catalog.items.ron");
+    embed_asset!(app, "animations/pillbug.anim.ron");
+    embed_asset!(app, "animations/ant.anim.ron");
+}
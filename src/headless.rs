@@ -0,0 +1,107 @@
+use std::time::{Duration, Instant};
+
+use bevy::{
+    app::PluginGroupBuilder,
+    audio::AudioSource,
+    gltf::GltfPlugin,
+    input::InputPlugin,
+    log::LogPlugin,
+    prelude::*,
+    render::{
+        mesh::MeshPlugin,
+        primitives::Aabb,
+        texture::ImagePlugin,
+        view::{InheritedVisibility, ViewVisibility},
+    },
+    scene::ScenePlugin,
+    state::app::StatesPlugin,
+    time::TimeUpdateStrategy,
+};
+
+use bevy_yarnspinner::prelude::YarnProject;
+
+use crate::{GamePlugin, ItemCatalogHandle, WorldScene};
+
+/// `MinimalPlugins` with what the game needs to run without a window, GPU, audio or gamepads.
+/// Assets, scenes and meshes still load, and the primary window exists as an entity only, so the
+/// cursor and camera systems keep working.
+pub struct HeadlessPlugins;
+
+impl PluginGroup for HeadlessPlugins {
+    fn build(self) -> PluginGroupBuilder {
+        MinimalPlugins
+            .build()
+            .add(LogPlugin::default())
+            .add(StatesPlugin)
+            .add(TransformPlugin)
+            .add(HierarchyPlugin)
+            .add(InputPlugin)
+            .add(WindowPlugin::default())
+            .add(AssetPlugin::default())
+            .add(ScenePlugin)
+            .add(HeadlessRenderPlugin)
+            .add(MeshPlugin)
+            .add(ImagePlugin::default())
+            .add(GltfPlugin::default())
+    }
+}
+
+/// What the render, PBR and audio plugins would otherwise provide: their asset types, so the game
+/// can load and create them, the components level scenes are made of, and the ambient light.
+struct HeadlessRenderPlugin;
+
+impl Plugin for HeadlessRenderPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<StandardMaterial>()
+            .register_asset_reflect::<StandardMaterial>()
+            .init_asset::<AudioSource>()
+            .register_type::<Aabb>()
+            .register_type::<Visibility>()
+            .register_type::<InheritedVisibility>()
+            .register_type::<ViewVisibility>()
+            .init_resource::<AmbientLight>();
+    }
+}
+
+/// The game running headless without its UI, stepped by calling `App::update`. Every update
+/// advances time by exactly one fixed timestep, so runs don't depend on how fast they go and input
+/// replays line up tick for tick.
+pub fn headless_app() -> App {
+    let mut app = App::new();
+    // Every test's app would try to install the global logger
+    app.add_plugins((HeadlessPlugins.build().disable::<LogPlugin>(), GamePlugin));
+    let frame_time = app.world().resource::<Time<Fixed>>().timestep();
+    app.insert_resource(TimeUpdateStrategy::ManualDuration(frame_time));
+    // Done by `App::run` otherwise, some plugins only register their asset loaders here
+    app.finish();
+    app.cleanup();
+    app
+}
+
+/// Whether the level, the item catalog and the Yarn project have loaded.
+pub fn game_assets_loaded(world: &mut World) -> bool {
+    let Some(scene) = world
+        .query_filtered::<&Handle<Scene>, With<WorldScene>>()
+        .iter(world)
+        .next()
+    else {
+        return false;
+    };
+    let asset_server = world.resource::<AssetServer>();
+    asset_server.is_loaded_with_dependencies(scene)
+        && asset_server.is_loaded_with_dependencies(&world.resource::<ItemCatalogHandle>().0)
+        && world.contains_resource::<YarnProject>()
+}
+
+/// Updates `app` until the game's assets have loaded, returning whether they did within `timeout`.
+/// Loading happens on other threads, so it takes however many frames it takes.
+pub fn wait_for_game_assets(app: &mut App, timeout: Duration) -> bool {
+    let started = Instant::now();
+    while !game_assets_loaded(app.world_mut()) {
+        if started.elapsed() > timeout {
+            return false;
+        }
+        app.update();
+    }
+    true
+}
@@ -1,8 +1,125 @@
+use std::f32::consts::PI;
+
 use bevy::prelude::*;
 
+use crate::GameplaySet;
+
+const HIGHLIGHT_COLOR: Color = Color::srgb(1.0, 1.0, 0.5);
+
+pub struct InteractablePlugin;
+
+impl Plugin for InteractablePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionTarget>()
+            .add_event::<InteractEvent>()
+            .add_systems(Update, highlight_interaction_target.in_set(GameplaySet));
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum InteractionKind {
+    Talk,
+    PickUp,
+    Push,
+    Read,
+    UseDoor,
+}
+
+impl InteractionKind {
+    fn default_prompt(&self) -> &'static str {
+        match self {
+            Self::Talk => "talk",
+            Self::PickUp => "pick up",
+            Self::Push => "push",
+            Self::Read => "read",
+            Self::UseDoor => "open",
+        }
+    }
+
+    fn default_range(&self) -> f32 {
+        match self {
+            Self::Talk | Self::Read | Self::UseDoor => 2.0,
+            Self::PickUp | Self::Push => 1.5,
+        }
+    }
+
+    fn default_max_angle(&self) -> f32 {
+        match self {
+            Self::Talk | Self::PickUp => PI,
+            Self::Push => PI / 4.0,
+            Self::Read | Self::UseDoor => PI / 2.0,
+        }
+    }
+}
+
 #[derive(Component, Debug)]
-pub struct Interactable;
+pub struct Interactable {
+    pub kind: InteractionKind,
+    /// Completes "Press E to ..." in the prompt shown above the entity.
+    pub prompt: String,
+    pub range: f32,
+    /// Largest angle between the player's facing and the direction to the entity, in radians.
+    pub max_angle: f32,
+}
+
+impl Interactable {
+    pub fn new(kind: InteractionKind) -> Self {
+        Self {
+            kind,
+            prompt: kind.default_prompt().to_string(),
+            range: kind.default_range(),
+            max_angle: kind.default_max_angle(),
+        }
+    }
+
+    pub fn with_prompt(mut self, prompt: impl Into<String>) -> Self {
+        self.prompt = prompt.into();
+        self
+    }
+}
 
 #[derive(Event, Debug)]
-pub struct InteractEvent(pub Entity);
+pub struct InteractEvent {
+    pub entity: Entity,
+    pub kind: InteractionKind,
+}
+
+/// The interactable the player would interact with when pressing interact.
+#[derive(Resource, Debug, Default, PartialEq, Eq, Deref, DerefMut)]
+pub struct InteractionTarget(pub Option<Entity>);
+
+/// Material an entity had before it was swapped for a highlighted copy.
+#[derive(Component, Debug)]
+struct Highlighted(Handle<StandardMaterial>);
+
+fn highlight_interaction_target(
+    target: Res<InteractionTarget>,
+    mut highlighted_query: Query<(Entity, &Highlighted, &mut Handle<StandardMaterial>)>,
+    mut material_query: Query<&mut Handle<StandardMaterial>, Without<Highlighted>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    mut commands: Commands,
+) {
+    if !target.is_changed() {
+        return;
+    }
+
+    for (entity, Highlighted(original), mut material) in highlighted_query.iter_mut() {
+        if Some(entity) != **target {
+            *material = original.clone();
+            commands.entity(entity).remove::<Highlighted>();
+        }
+    }
 
+    if let Some(entity) = **target {
+        if let Ok(mut material) = material_query.get_mut(entity) {
+            let Some(mut highlighted) = materials.get(material.id()).cloned() else {
+                return;
+            };
+            highlighted.base_color = HIGHLIGHT_COLOR;
+            commands
+                .entity(entity)
+                .insert(Highlighted(material.clone()));
+            *material = materials.add(highlighted);
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext, LoadState, ParseAssetPathError},
+    ecs::system::SystemParam,
+    prelude::*,
+    utils::HashMap,
+};
+use serde::Deserialize;
+
+use crate::game_asset;
+
+#[derive(Debug, Clone)]
+pub struct ItemDefinition {
+    pub name: String,
+    pub icon: Handle<Image>,
+    pub description: String,
+    pub stack_size: u32,
+}
+
+/// Every item in the game, keyed by the id used in `Item_<id>` nodes and Yarn commands.
+#[derive(Asset, TypePath, Debug, Deref)]
+pub struct ItemCatalog(HashMap<String, ItemDefinition>);
+
+#[derive(Resource, Debug)]
+pub struct ItemCatalogHandle(pub Handle<ItemCatalog>);
+
+#[derive(SystemParam)]
+pub struct ItemCatalogParam<'w> {
+    handle: Res<'w, ItemCatalogHandle>,
+    catalogs: Res<'w, Assets<ItemCatalog>>,
+}
+
+impl ItemCatalogParam<'_> {
+    pub fn get(&self, item: &str) -> Option<&ItemDefinition> {
+        self.catalogs.get(&self.handle.0)?.get(item)
+    }
+}
+
+/// Whether the item catalog has finished loading, successfully or not.
+pub fn item_catalog_loaded(handle: Res<ItemCatalogHandle>, asset_server: Res<AssetServer>) -> bool {
+    matches!(
+        asset_server.load_state(&handle.0),
+        LoadState::Loaded | LoadState::Failed(_)
+    )
+}
+
+#[derive(Deserialize)]
+struct ItemDefinitionFile {
+    name: String,
+    icon: String,
+    description: String,
+    stack_size: u32,
+}
+
+#[derive(Default)]
+pub struct ItemCatalogLoader;
+
+impl AssetLoader for ItemCatalogLoader {
+    type Asset = ItemCatalog;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<ItemCatalog, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let definitions: HashMap<String, ItemDefinitionFile> = ron::de::from_bytes(&bytes)?;
+        Ok(ItemCatalog(
+            definitions
+                .into_iter()
+                .map(|(id, definition)| {
+                    // Icons are relative to the catalog, so it loads the same embedded or not
+                    let icon = load_context.asset_path().resolve_embed(&definition.icon)?;
+                    let item = ItemDefinition {
+                        name: definition.name,
+                        icon: load_context.load(icon),
+                        description: definition.description,
+                        stack_size: definition.stack_size,
+                    };
+                    Ok((id, item))
+                })
+                .collect::<Result<_, ParseAssetPathError>>()?,
+        ))
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["items.ron"]
+    }
+}
+
+pub(super) fn load_item_catalog(mut commands: Commands, asset_server: Res<AssetServer>) {
+    commands.insert_resource(ItemCatalogHandle(
+        asset_server.load(game_asset("items/catalog.items.ron")),
+    ));
+}
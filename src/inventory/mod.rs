@@ -0,0 +1,238 @@
+use std::sync::{Arc, RwLock};
+
+use bevy::{prelude::*, utils::HashMap};
+
+use crate::{AppState, GameplaySet, InteractEvent, InteractionKind, Player, YarnRegistryAppExt};
+
+mod item;
+pub use item::*;
+
+pub const INVENTORY_SLOTS: usize = 12;
+
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        let contents = InventoryContents::default();
+        app.init_asset::<ItemCatalog>()
+            .init_asset_loader::<ItemCatalogLoader>()
+            .insert_resource(contents.clone())
+            .add_systems(Startup, load_item_catalog)
+            .add_systems(OnEnter(AppState::InGame), clear_inventory_contents)
+            .add_systems(
+                Update,
+                (handle_pickup_interactions, sync_inventory_contents)
+                    .chain()
+                    .in_set(GameplaySet),
+            )
+            .add_yarn_command("give_item", give_item_command)
+            .add_yarn_command("take_item", take_item_command)
+            .add_yarn_function("has_item", {
+                let contents = contents.clone();
+                move |item: String| contents.count(&item) > 0
+            })
+            .add_yarn_function("item_count", move |item: String| {
+                contents.count(&item) as f32
+            });
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemStack {
+    pub item: String,
+    pub count: u32,
+}
+
+#[derive(Component, Debug, Default)]
+pub struct Inventory {
+    pub stacks: Vec<ItemStack>,
+}
+
+impl Inventory {
+    pub fn count(&self, item: &str) -> u32 {
+        self.stacks
+            .iter()
+            .filter(|stack| stack.item == item)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    /// Adds up to `count` of `item`, filling existing stacks first. Returns how many did not fit.
+    pub fn add(&mut self, item: &str, mut count: u32, stack_size: u32) -> u32 {
+        let stack_size = stack_size.max(1);
+        for stack in self.stacks.iter_mut().filter(|stack| stack.item == item) {
+            let added = count.min(stack_size.saturating_sub(stack.count));
+            stack.count += added;
+            count -= added;
+        }
+        while count > 0 && self.stacks.len() < INVENTORY_SLOTS {
+            let added = count.min(stack_size);
+            self.stacks.push(ItemStack {
+                item: item.to_string(),
+                count: added,
+            });
+            count -= added;
+        }
+        count
+    }
+
+    /// Removes `count` of `item` if the inventory holds that many, otherwise leaves it untouched.
+    pub fn remove(&mut self, item: &str, mut count: u32) -> bool {
+        if self.count(item) < count {
+            return false;
+        }
+        for stack in self
+            .stacks
+            .iter_mut()
+            .rev()
+            .filter(|stack| stack.item == item)
+        {
+            let removed = count.min(stack.count);
+            stack.count -= removed;
+            count -= removed;
+        }
+        self.stacks.retain(|stack| stack.count > 0);
+        true
+    }
+}
+
+/// Item counts of the player's inventory that Yarn functions can read while a dialogue is running.
+#[derive(Resource, Clone, Default)]
+pub struct InventoryContents(Arc<RwLock<HashMap<String, u32>>>);
+
+impl InventoryContents {
+    pub fn count(&self, item: &str) -> u32 {
+        self.0
+            .read()
+            .unwrap()
+            .get(item)
+            .copied()
+            .unwrap_or_default()
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Pickup {
+    pub item: String,
+    pub count: u32,
+}
+
+fn clear_inventory_contents(contents: Res<InventoryContents>) {
+    contents.0.write().unwrap().clear();
+}
+
+fn sync_inventory_contents(
+    inventory_query: Query<&Inventory, (With<Player>, Changed<Inventory>)>,
+    contents: Res<InventoryContents>,
+) {
+    if let Ok(inventory) = inventory_query.get_single() {
+        let mut contents = contents.0.write().unwrap();
+        contents.clear();
+        for stack in inventory.stacks.iter() {
+            *contents.entry(stack.item.clone()).or_default() += stack.count;
+        }
+    }
+}
+
+fn handle_pickup_interactions(
+    mut pickup_query: Query<&mut Pickup>,
+    mut inventory_query: Query<&mut Inventory, With<Player>>,
+    mut event_reader: EventReader<InteractEvent>,
+    item_catalog: ItemCatalogParam,
+    mut commands: Commands,
+) {
+    for InteractEvent { entity, .. } in event_reader
+        .read()
+        .filter(|event| event.kind == InteractionKind::PickUp)
+    {
+        if let Ok(mut pickup) = pickup_query.get_mut(*entity) {
+            let Some(definition) = item_catalog.get(&pickup.item) else {
+                warn!("Unknown item {:?}", pickup.item);
+                continue;
+            };
+            let mut inventory = inventory_query.single_mut();
+            pickup.count = inventory.add(&pickup.item, pickup.count, definition.stack_size);
+            if pickup.count == 0 {
+                commands.entity(*entity).despawn_recursive();
+            }
+        }
+    }
+}
+
+// <<give_item item count>>
+fn give_item_command(
+    In((item, count)): In<(String, u32)>,
+    mut inventory_query: Query<&mut Inventory, With<Player>>,
+    item_catalog: ItemCatalogParam,
+) {
+    let Some(definition) = item_catalog.get(&item) else {
+        warn!("Unknown item {:?}", item);
+        return;
+    };
+    let mut inventory = inventory_query.single_mut();
+    let leftover = inventory.add(&item, count, definition.stack_size);
+    if leftover > 0 {
+        warn!("Inventory full, dropped {} {:?}", leftover, item);
+    }
+}
+
+// <<take_item item count>>
+fn take_item_command(
+    In((item, count)): In<(String, u32)>,
+    mut inventory_query: Query<&mut Inventory, With<Player>>,
+) {
+    let mut inventory = inventory_query.single_mut();
+    if !inventory.remove(&item, count) {
+        warn!("Player does not have {} {:?}", count, item);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn stack(item: &str, count: u32) -> ItemStack {
+        ItemStack {
+            item: item.to_string(),
+            count,
+        }
+    }
+
+    #[test]
+    fn add_fills_existing_stacks_first() {
+        let mut inventory = Inventory {
+            stacks: vec![stack("seed", 3), stack("pebble", 1)],
+        };
+        assert_eq!(inventory.add("seed", 4, 5), 0);
+        assert_eq!(
+            inventory.stacks,
+            vec![stack("seed", 5), stack("pebble", 1), stack("seed", 2)]
+        );
+        assert_eq!(inventory.count("seed"), 7);
+    }
+
+    #[test]
+    fn add_returns_what_does_not_fit() {
+        let mut inventory = Inventory::default();
+        let leftover = inventory.add("seed", INVENTORY_SLOTS as u32 * 2 + 3, 2);
+        assert_eq!(leftover, 3);
+        assert_eq!(inventory.stacks.len(), INVENTORY_SLOTS);
+        assert_eq!(inventory.add("pebble", 1, 10), 1);
+        assert_eq!(inventory.count("pebble"), 0);
+    }
+
+    #[test]
+    fn remove_is_all_or_nothing() {
+        let mut inventory = Inventory {
+            stacks: vec![stack("seed", 5), stack("seed", 2)],
+        };
+        assert!(!inventory.remove("seed", 8));
+        assert_eq!(inventory.stacks, vec![stack("seed", 5), stack("seed", 2)]);
+        assert!(!inventory.remove("pebble", 1));
+
+        assert!(inventory.remove("seed", 4));
+        assert_eq!(inventory.stacks, vec![stack("seed", 3)]);
+        assert!(inventory.remove("seed", 3));
+        assert!(inventory.stacks.is_empty());
+    }
+}
@@ -0,0 +1,146 @@
+use avian3d::PhysicsPlugins;
+use bevy::{ecs::schedule::ScheduleLabel, prelude::*};
+
+mod ui;
+pub use ui::*;
+
+mod player;
+pub use player::*;
+
+mod camera;
+pub use camera::*;
+
+mod bugoid;
+pub use bugoid::*;
+
+mod world;
+pub use world::*;
+
+mod quests;
+pub use quests::*;
+
+mod embedded_assets;
+pub use embedded_assets::*;
+
+mod dialog;
+pub use dialog::*;
+
+mod interactable;
+pub use interactable::*;
+
+mod npc;
+pub use npc::*;
+
+mod billboard;
+pub use billboard::*;
+
+mod inventory;
+pub use inventory::*;
+
+mod door;
+pub use door::*;
+
+mod pushable;
+pub use pushable::*;
+
+mod sign;
+pub use sign::*;
+mod sprite_animation;
+pub use sprite_animation::*;
+mod time_of_day;
+pub use time_of_day::*;
+mod weather;
+pub use weather::*;
+mod replay;
+pub use replay::*;
+#[cfg(feature = "dev")]
+mod dev;
+#[cfg(feature = "dev")]
+pub use dev::*;
+pub mod headless;
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum AppState {
+    #[default]
+    MainMenu,
+    InGame,
+}
+
+#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
+pub enum PausedState {
+    Paused,
+    #[default]
+    Running,
+}
+
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GameplaySet;
+
+/// Rate of the fixed timestep that physics, player input and input replays run at.
+pub const FIXED_UPDATE_HZ: f64 = 60.0;
+
+#[derive(Component, Debug)]
+pub struct GameObject;
+
+/// The whole game except its UI, on top of the engine plugins from either `DefaultPlugins` or
+/// `HeadlessPlugins`. Builds with a window add `UiPlugin` as well.
+pub struct GamePlugin;
+
+impl Plugin for GamePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins((
+            PhysicsPlugins::new(FixedPostUpdate),
+            WorldPlugin,
+            PlayerPlugin,
+            CameraPlugin,
+            DialogPlugin,
+            NpcPlugin,
+            BillboardPlugin,
+            QuestsPlugin,
+            BugoidPlugin,
+            InventoryPlugin,
+        ))
+        .add_plugins((
+            InteractablePlugin,
+            DoorPlugin,
+            PushablePlugin,
+            SignPlugin,
+            SpriteAnimationPlugin,
+            TimeOfDayPlugin,
+            WeatherPlugin,
+            ReplayPlugin,
+        ))
+        .insert_resource(Time::<Fixed>::from_hz(FIXED_UPDATE_HZ))
+        .init_state::<AppState>()
+        .init_state::<PausedState>()
+        .add_systems(OnExit(AppState::InGame), clean_up_game);
+        configure_gameplay_set(app);
+        embed_assets(app);
+    }
+}
+
+/// Runs `GameplaySet` in every schedule it is used in only while a game is running and its level
+/// has loaded, so nothing moves before the ground is there and runs play out the same however long
+/// loading takes.
+fn configure_gameplay_set(app: &mut App) {
+    for schedule in [
+        Update.intern(),
+        PostUpdate.intern(),
+        FixedPreUpdate.intern(),
+        FixedUpdate.intern(),
+    ] {
+        app.configure_sets(
+            schedule,
+            GameplaySet
+                .run_if(in_state(AppState::InGame))
+                .run_if(in_state(PausedState::Running))
+                .run_if(world_loaded),
+        );
+    }
+}
+
+fn clean_up_game(mut commands: Commands, query: Query<Entity, With<GameObject>>) {
+    for entity in query.iter() {
+        commands.entity(entity).despawn_recursive();
+    }
+}
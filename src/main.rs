@@ -1,93 +1,40 @@
-use avian3d::{debug_render::PhysicsDebugPlugin, PhysicsPlugins};
 use bevy::prelude::*;
-
-mod ui;
-use ui::*;
-
-mod player;
-use player::*;
-
-mod camera;
-use camera::*;
-
-mod bugoid;
-use bugoid::*;
-
-mod world;
-use world::*;
-
-mod quests;
-use quests::*;
-
-mod embedded_assets;
-use embedded_assets::*;
-
-mod dialog;
-use dialog::*;
-
-mod interactable;
-use interactable::*;
-
-mod npc;
-use npc::*;
-
-mod billboard;
-use billboard::*;
-
-#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
-pub enum AppState {
-    #[default]
-    MainMenu,
-    InGame,
-}
-
-#[derive(States, Default, Debug, Clone, PartialEq, Eq, Hash)]
-pub enum PausedState {
-    Paused,
-    #[default]
-    Running,
-}
-
-#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
-pub struct GameplaySet;
-
-#[derive(Component, Debug)]
-struct GameObject;
+use ludum_dare_56::{GamePlugin, InputRecording, InputReplay, UiPlugin};
 
 fn main() {
     let mut app = App::new();
-    app.add_plugins((
-        DefaultPlugins,
-        PhysicsPlugins::default(),
-        UiPlugin,
-        WorldPlugin,
-        PlayerPlugin,
-        CameraPlugin,
-        DialogPlugin,
-        NpcPlugin,
-        BillboardPlugin,
-    ))
-    .init_state::<AppState>()
-    .init_state::<PausedState>()
-    .add_systems(OnExit(AppState::InGame), clean_up_game)
-    .configure_sets(
-        Update,
-        (GameplaySet
-            .run_if(in_state(AppState::InGame))
-            .run_if(in_state(PausedState::Running)),),
-    )
-    .configure_sets(
-        PostUpdate,
-        (GameplaySet
-            .run_if(in_state(AppState::InGame))
-            .run_if(in_state(PausedState::Running)),),
-    );
-    embed_assets(&mut app);
+    app.add_plugins((DefaultPlugins, GamePlugin, UiPlugin))
+        .insert_resource(input_replay_from_args());
+    #[cfg(feature = "dev")]
+    app.add_plugins(ludum_dare_56::DevPlugin);
     app.run();
 }
 
-fn clean_up_game(mut commands: Commands, query: Query<Entity, With<GameObject>>) {
-    for entity in query.iter() {
-        commands.entity(entity).despawn_recursive();
+/// `--record <path>` saves this game's input to `path`, `--replay <path>` plays it back.
+fn input_replay_from_args() -> InputReplay {
+    let args: Vec<String> = std::env::args().collect();
+    match args
+        .iter()
+        .skip(1)
+        .map(String::as_str)
+        .collect::<Vec<_>>()
+        .as_slice()
+    {
+        ["--record", path] => InputReplay::Recording {
+            recording: InputRecording::default(),
+            path: Some(path.into()),
+        },
+        ["--replay", path] => match InputRecording::load(path) {
+            Ok(recording) => InputReplay::replay(&recording),
+            Err(error) => {
+                eprintln!("Couldn't load input recording {}: {}", path, error);
+                std::process::exit(1);
+            }
+        },
+        [] => InputReplay::Off,
+        _ => {
+            eprintln!("Usage: {} [--record <path> | --replay <path>]", args[0]);
+            std::process::exit(1);
+        }
     }
 }
@@ -1,30 +1,192 @@
-use bevy::prelude::*;
-use bevy_yarnspinner::prelude::YarnProject;
+use bevy::{gltf::GltfExtras, prelude::*};
+use bevy_yarnspinner::prelude::{DialogueRunner, YarnProject};
+use serde::Deserialize;
 
-use crate::{GameplaySet, InteractEvent};
+use crate::{
+    Billboard, DialogueSpeaker, GameplaySet, InteractEvent, InteractionKind, Player,
+    SpriteAnimator, TimeOfDay, YarnFunctionRegistry, YarnRegistryAppExt, IDLE_CLIP, TALK_CLIP,
+    WALK_CLIP,
+};
+
+const NPC_WALK_SPEED: f32 = 2.0;
+/// Distance from a schedule location at which an NPC counts as having arrived.
+const NPC_ARRIVAL_DISTANCE: f32 = 0.1;
+/// Distance an NPC sent to the player keeps from them.
+const NPC_FOLLOW_DISTANCE: f32 = 1.5;
 
 pub struct NpcPlugin;
 
 impl Plugin for NpcPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, handle_npc_interactions.in_set(GameplaySet));
+        app.add_systems(
+            Update,
+            (
+                handle_npc_interactions,
+                (follow_npc_schedules, animate_npcs).chain(),
+            )
+                .in_set(GameplaySet),
+        )
+        .add_yarn_command("move_npc", move_npc_command);
     }
 }
 
 #[derive(Component, Debug)]
 pub struct Npc(pub String);
 
+/// Where an NPC spends each part of the day, read from the `schedule` property of its glTF node.
+#[derive(Component, Debug, Deserialize)]
+pub struct NpcSchedule {
+    #[serde(rename = "schedule")]
+    pub entries: Vec<NpcScheduleEntry>,
+    #[serde(skip)]
+    walking: bool,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct NpcScheduleEntry {
+    /// Hour the entry starts at, 0 to 24.
+    pub from: f32,
+    /// Hour the entry ends at, which may be past midnight.
+    pub to: f32,
+    /// Name of the node the NPC walks to.
+    pub location: String,
+    /// Yarn node started when talking to the NPC during this entry.
+    pub node: Option<String>,
+}
+
+impl NpcSchedule {
+    pub fn from_extras(extras: &GltfExtras) -> Option<Self> {
+        serde_json::from_str(&extras.value).ok()
+    }
+
+    /// A schedule that keeps the NPC at `location` all day.
+    pub fn staying_at(location: impl Into<String>) -> Self {
+        Self {
+            entries: vec![NpcScheduleEntry {
+                from: 0.0,
+                to: 24.0,
+                location: location.into(),
+                node: None,
+            }],
+            walking: false,
+        }
+    }
+
+    pub fn current_entry(&self, time_of_day: &TimeOfDay) -> Option<&NpcScheduleEntry> {
+        self.entries
+            .iter()
+            .find(|entry| time_of_day.is_between(entry.from, entry.to))
+    }
+}
+
 fn handle_npc_interactions(
     npc_query: Query<&Npc>,
     mut event_reader: EventReader<InteractEvent>,
     project: Res<YarnProject>,
+    function_registry: Res<YarnFunctionRegistry>,
     mut commands: Commands,
 ) {
-    for InteractEvent(entity) in event_reader.read() {
+    for InteractEvent { entity, .. } in event_reader
+        .read()
+        .filter(|event| event.kind == InteractionKind::Talk)
+    {
         if let Ok(Npc(node)) = npc_query.get(*entity) {
-            let mut dialog_runner = project.create_dialogue_runner();
+            let mut dialog_runner = function_registry.create_dialogue_runner(&project);
             dialog_runner.start_node(node);
-            commands.spawn(dialog_runner);
+            commands.spawn((dialog_runner, DialogueSpeaker(*entity)));
+        }
+    }
+}
+
+fn follow_npc_schedules(
+    time_of_day: Res<TimeOfDay>,
+    speaker_query: Query<&DialogueSpeaker, With<DialogueRunner>>,
+    location_query: Query<(&Name, &GlobalTransform, Has<Player>)>,
+    mut npc_query: Query<(
+        Entity,
+        &mut Npc,
+        &mut NpcSchedule,
+        &mut Transform,
+        Option<&mut Billboard>,
+    )>,
+    time: Res<Time>,
+) {
+    for (entity, mut npc, mut schedule, mut transform, billboard) in npc_query.iter_mut() {
+        schedule.walking = false;
+        let Some(entry) = schedule.current_entry(&time_of_day).cloned() else {
+            continue;
+        };
+        if let Some(node) = entry.node {
+            if npc.0 != node {
+                npc.0 = node;
+            }
+        }
+
+        // Finish the conversation before wandering off
+        if speaker_query
+            .iter()
+            .any(|DialogueSpeaker(speaker)| *speaker == entity)
+        {
+            continue;
+        }
+        let Some((_, location_transform, is_player)) = location_query
+            .iter()
+            .find(|(name, ..)| name.as_str() == entry.location)
+        else {
+            continue;
+        };
+        // NPCs don't fall, so they stay at the height they were placed at
+        let offset = (location_transform.translation() - transform.translation).with_y(0.0);
+        let distance = offset.length();
+        let arrival_distance = if is_player {
+            NPC_FOLLOW_DISTANCE
+        } else {
+            NPC_ARRIVAL_DISTANCE
+        };
+        if distance <= arrival_distance {
+            continue;
+        }
+        let direction = offset / distance;
+        transform.translation +=
+            direction * (distance - arrival_distance).min(NPC_WALK_SPEED * time.delta_seconds());
+        schedule.walking = true;
+        if let Some(mut billboard) = billboard {
+            billboard.facing = direction;
         }
     }
 }
+
+fn animate_npcs(
+    speaker_query: Query<&DialogueSpeaker, With<DialogueRunner>>,
+    mut npc_query: Query<(Entity, &mut SpriteAnimator, Option<&NpcSchedule>), With<Npc>>,
+) {
+    for (entity, mut animator, schedule) in npc_query.iter_mut() {
+        let speaking = speaker_query
+            .iter()
+            .any(|DialogueSpeaker(speaker)| *speaker == entity);
+        let walking = schedule.is_some_and(|schedule| schedule.walking);
+        animator.play(if speaking {
+            TALK_CLIP
+        } else if walking {
+            WALK_CLIP
+        } else {
+            IDLE_CLIP
+        });
+    }
+}
+
+// <<move_npc npc location>> sends the NPC to the node named `location`, or after the player if it is
+// `Player`, for the rest of the game
+fn move_npc_command(
+    In((npc, location)): In<(String, String)>,
+    npc_query: Query<(Entity, &Name), With<Npc>>,
+    mut commands: Commands,
+) {
+    let Some((entity, _)) = npc_query.iter().find(|(_, name)| name.as_str() == npc) else {
+        warn!("Unknown NPC {:?}", npc);
+        return;
+    };
+    commands
+        .entity(entity)
+        .insert(NpcSchedule::staying_at(location));
+}
@@ -0,0 +1,56 @@
+use bevy::prelude::*;
+
+/// Draws an entity moved by physics between its last two fixed-step positions, so it moves smoothly
+/// at frame rates that don't match the fixed timestep. Physics always sees the latest step's
+/// position, and a translation set outside of physics, like a door's teleport, is kept as is.
+#[derive(Component, Debug)]
+pub struct InterpolatedTranslation {
+    previous: Vec3,
+    current: Vec3,
+    drawn: Vec3,
+}
+
+impl InterpolatedTranslation {
+    pub fn new(translation: Vec3) -> Self {
+        Self {
+            previous: translation,
+            current: translation,
+            drawn: translation,
+        }
+    }
+}
+
+/// Puts back the latest step's translation before the next step runs.
+pub(super) fn restore_translation(
+    mut query: Query<(&mut Transform, &mut InterpolatedTranslation)>,
+) {
+    for (mut transform, mut interpolated) in query.iter_mut() {
+        if transform.translation != interpolated.drawn {
+            *interpolated = InterpolatedTranslation::new(transform.translation);
+        } else if transform.translation != interpolated.current {
+            transform.translation = interpolated.current;
+        }
+    }
+}
+
+pub(super) fn record_translation(mut query: Query<(&Transform, &mut InterpolatedTranslation)>) {
+    for (transform, mut interpolated) in query.iter_mut() {
+        interpolated.previous = interpolated.current;
+        interpolated.current = transform.translation;
+        interpolated.drawn = transform.translation;
+    }
+}
+
+pub(super) fn interpolate_translation(
+    mut query: Query<(&mut Transform, &mut InterpolatedTranslation)>,
+    fixed_time: Res<Time<Fixed>>,
+) {
+    let blend = fixed_time.overstep_fraction();
+    for (mut transform, mut interpolated) in query.iter_mut() {
+        if transform.translation != interpolated.drawn {
+            continue;
+        }
+        interpolated.drawn = interpolated.previous.lerp(interpolated.current, blend);
+        transform.translation = interpolated.drawn;
+    }
+}
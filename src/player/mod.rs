@@ -1,13 +1,12 @@
-use std::f32::consts::PI;
-
 use avian3d::{
     collision::Collider,
-    prelude::{LockedAxes, RigidBody},
+    prelude::{LinearVelocity, LockedAxes, RigidBody},
 };
-use bevy::{pbr::NotShadowCaster, prelude::*, render::mesh::PlaneMeshBuilder};
+use bevy::{prelude::*, sprite::Anchor};
 use bevy_tnua::{
     builtins::TnuaBuiltinWalk,
     controller::{TnuaController, TnuaControllerBundle, TnuaControllerPlugin},
+    TnuaUserControlsSystemSet,
 };
 use bevy_tnua_avian3d::{TnuaAvian3dPlugin, TnuaAvian3dSensorShape};
 use bevy_yarnspinner::prelude::DialogueRunner;
@@ -17,10 +16,21 @@ use leafwing_input_manager::{
 };
 
 use crate::{
-    dialog, AppState, Billboard, GameCamera, GameObject, GameplaySet, InteractEvent, Interactable,
+    game_asset, AppState, Billboard, BillboardMode, BillboardSprite, BillboardSprites, BlobShadow,
+    GameCamera, GameObject, GameplaySet, InteractEvent, Interactable, InteractionTarget, Inventory,
+    SpriteAnimator, IDLE_CLIP, WALK_CLIP,
 };
 
+mod interpolation;
+pub use interpolation::*;
+
 const PLAYER_WALK_SPEED: f32 = 5.0;
+/// Exponential decay rate for turning the player towards the direction it is moving in.
+const PLAYER_TURN_DAMPING: f32 = 12.0;
+/// Horizontal speed below which the player keeps facing the way it was.
+const PLAYER_TURN_MIN_SPEED: f32 = 0.5;
+/// How far the facing has to point to one side of the screen before the sprite flips.
+const SPRITE_FLIP_THRESHOLD: f32 = 0.2;
 
 pub struct PlayerPlugin;
 
@@ -28,20 +38,33 @@ impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_plugins((
             InputManagerPlugin::<PlayerAction>::default(),
-            TnuaControllerPlugin::default(),
-            TnuaAvian3dPlugin::default(),
+            // Same schedule as physics, so replayed input meets the same physics steps
+            TnuaControllerPlugin::new(FixedUpdate),
+            TnuaAvian3dPlugin::new(FixedUpdate),
         ))
         .insert_state(PlayerState::Free)
-        .add_event::<InteractEvent>()
         .add_systems(OnEnter(AppState::InGame), setup_player)
         .add_systems(
-            Update,
+            FixedUpdate,
             (
-                (move_player)
-                    .in_set(GameplaySet)
+                move_player
+                    .in_set(TnuaUserControlsSystemSet)
                     .run_if(in_state(PlayerState::Free)),
-                (handle_player_interaction, update_player_look_direction).in_set(GameplaySet),
-            ),
+                (update_interaction_target, handle_player_interaction).chain(),
+            )
+                .in_set(GameplaySet),
+        )
+        .add_systems(FixedFirst, restore_translation)
+        .add_systems(FixedLast, record_translation)
+        // Before anything draws or follows the player
+        .add_systems(Update, interpolate_translation.before(GameplaySet))
+        .add_systems(
+            Update,
+            (
+                (update_player_facing, update_player_sprite).chain(),
+                update_player_animation,
+            )
+                .in_set(GameplaySet),
         );
     }
 }
@@ -72,19 +95,36 @@ impl Actionlike for PlayerAction {
 #[derive(Component, Debug)]
 pub struct Player;
 
-fn setup_player(mut commands: Commands, asset_server: Res<AssetServer>) {
-    let pillbug_texture = asset_server.load("embedded://ludum_dare_56/textures/pillbug.png");
+/// The player's billboard sprite, a child of the player which turns to face where it is going.
+/// Without directional frames, sprites are drawn facing right and mirrored when the player faces
+/// left on screen.
+#[derive(Component, Debug, Default)]
+pub struct PlayerSprite {
+    flipped: bool,
+}
+
+fn setup_player(
+    mut commands: Commands,
+    asset_server: Res<AssetServer>,
+    mut sprites: BillboardSprites,
+) {
+    let pillbug_texture = asset_server.load(game_asset("textures/pillbug.png"));
+    let pillbug_animations = asset_server.load(game_asset("animations/pillbug.anim.ron"));
+    let translation = Vec3::new(0.0, 2.0, 0.0);
     commands
         .spawn((
             GameObject,
             Player,
+            Name::new("Player"),
+            Inventory::default(),
+            InterpolatedTranslation::new(translation),
             RigidBody::Dynamic,
             Collider::capsule(0.5, 1.0),
             TnuaControllerBundle::default(),
             TnuaAvian3dSensorShape(Collider::cylinder(0.49, 0.0)),
             LockedAxes::ROTATION_LOCKED,
             SpatialBundle {
-                transform: Transform::from_translation(Vec3::new(0.0, 2.0, 0.0)),
+                transform: Transform::from_translation(translation),
                 ..Default::default()
             },
             InputManagerBundle::with_map(
@@ -97,37 +137,32 @@ fn setup_player(mut commands: Commands, asset_server: Res<AssetServer>) {
         ))
         .with_children(|parent| {
             parent.spawn((
-                PbrBundle {
-                    mesh: asset_server.add(
-                        PlaneMeshBuilder::new(Dir3::NEG_Z, Vec2::splat(2.0))
-                            .build()
-                            .rotated_by(Quat::from_axis_angle(Vec3::NEG_Z, PI)),
-                    ),
-                    material: asset_server.add(StandardMaterial {
-                        base_color_texture: Some(pillbug_texture),
-                        alpha_mode: AlphaMode::Blend,
-                        unlit: true,
-                        ..Default::default()
-                    }),
-                    transform: Transform::from_rotation(Quat::from_axis_angle(Vec3::Y, PI)).with_translation(Vec3::new(0.0, -0.5, 0.0)),
-                    ..Default::default()
-                },
-                NotShadowCaster,
+                sprites
+                    .bundle(
+                        &BillboardSprite::new(pillbug_texture)
+                            .with_size(Vec2::splat(2.0))
+                            .with_anchor(Anchor::BottomCenter),
+                    )
+                    .with_billboard(Billboard::default().with_mode(BillboardMode::Cylindrical))
+                    .with_transform(Transform::from_xyz(0.0, -1.5, 0.0)),
+                BlobShadow { radius: 0.6 },
+                SpriteAnimator::new(pillbug_animations),
+                PlayerSprite::default(),
             ));
         });
 }
 
 fn move_player(
-    mut player_query: Query<
-        (&Transform, &mut TnuaController, &ActionState<PlayerAction>),
-        With<Player>,
-    >,
+    mut player_query: Query<(&mut TnuaController, &ActionState<PlayerAction>), With<Player>>,
+    camera_query: Query<&Transform, With<GameCamera>>,
 ) {
-    let (transform, mut controller, action_state) = player_query.single_mut();
+    let (mut controller, action_state) = player_query.single_mut();
+    let camera_transform = camera_query.single();
+    let forward = camera_transform.forward().with_y(0.0).normalize_or_zero();
+    let right = camera_transform.right().with_y(0.0).normalize_or_zero();
 
     if let Some(walk_input) = action_state.dual_axis_data(&PlayerAction::Walk) {
-        let walk_dir =
-            walk_input.pair.x * transform.right() + walk_input.pair.y * transform.forward();
+        let walk_dir = walk_input.pair.x * right + walk_input.pair.y * forward;
 
         controller.basis(TnuaBuiltinWalk {
             desired_velocity: walk_dir.normalize_or_zero() * PLAYER_WALK_SPEED,
@@ -137,56 +172,128 @@ fn move_player(
     }
 }
 
-fn update_player_look_direction(
-    player_query: Query<Entity, With<Player>>,
-    camera_query: Query<Entity, With<GameCamera>>,
-    mut transform_query: Query<&mut Transform>,
+fn update_player_facing(
+    mut player_query: Query<(&mut Transform, &LinearVelocity), With<Player>>,
+    time: Res<Time>,
 ) {
-    let player_entity = player_query.single();
-    let camera_entity = camera_query.single();
-    let [mut player_transform, camera_transform] =
-        transform_query.many_mut([player_entity, camera_entity]);
-    let camera_offset = camera_transform.translation - player_transform.translation;
-    let look_direction = Vec3::new(-camera_offset.x, 0.0, -camera_offset.z).normalize_or_zero();
-    player_transform.look_to(look_direction, Vec3::Y);
+    // Physics adds the velocity at the end of the frame the player is spawned in
+    let Ok((mut player_transform, velocity)) = player_query.get_single_mut() else {
+        return;
+    };
+    let horizontal_velocity = velocity.0.with_y(0.0);
+    if horizontal_velocity.length() < PLAYER_TURN_MIN_SPEED {
+        return;
+    }
+    let target_rotation = Transform::IDENTITY
+        .looking_to(horizontal_velocity, Vec3::Y)
+        .rotation;
+    let turn_blend = 1.0 - f32::exp(-PLAYER_TURN_DAMPING * time.delta_seconds());
+    player_transform.rotation = player_transform.rotation.slerp(target_rotation, turn_blend);
+}
+
+#[allow(clippy::type_complexity)]
+fn update_player_sprite(
+    player_query: Query<&Transform, With<Player>>,
+    camera_query: Query<&Transform, With<GameCamera>>,
+    mut sprite_query: Query<
+        (&mut Transform, &mut PlayerSprite, &mut Billboard),
+        (Without<Player>, Without<GameCamera>),
+    >,
+) {
+    let player_transform = player_query.single();
+    let camera_transform = camera_query.single();
+    let (mut sprite_transform, mut sprite, mut billboard) = sprite_query.single_mut();
+    billboard.facing = *player_transform.forward();
+
+    let sideways = player_transform.forward().dot(*camera_transform.right());
+    if billboard.directions.is_some() {
+        sprite.flipped = false;
+    } else if sideways.abs() > SPRITE_FLIP_THRESHOLD {
+        sprite.flipped = sideways < 0.0;
+    }
+    sprite_transform.scale.x = if sprite.flipped { -1.0 } else { 1.0 };
+}
+
+fn update_player_animation(
+    player_query: Query<&TnuaController, With<Player>>,
+    mut animator_query: Query<&mut SpriteAnimator, With<PlayerSprite>>,
+) {
+    let controller = player_query.single();
+    let walking = controller
+        .concrete_basis::<TnuaBuiltinWalk>()
+        .is_some_and(|(_, state)| state.running_velocity.length() > PLAYER_TURN_MIN_SPEED);
+    animator_query
+        .single_mut()
+        .play(if walking { WALK_CLIP } else { IDLE_CLIP });
+}
+
+fn update_interaction_target(
+    state: Res<State<PlayerState>>,
+    player_query: Query<&Transform, With<Player>>,
+    interactable_query: Query<(Entity, &Transform, &Interactable), Without<Player>>,
+    mut target: ResMut<InteractionTarget>,
+) {
+    let player_transform = player_query.single();
+    let mut closest = (None, f32::INFINITY);
+    if *state.get() == PlayerState::Free {
+        for (interactable_entity, interactable_transform, interactable) in interactable_query.iter()
+        {
+            if player_transform
+                .translation
+                .distance_squared(interactable_transform.translation)
+                < (interactable.range * interactable.range)
+            {
+                // Facing is horizontal, so things on the ground count as straight ahead
+                let interactable_arccosine = f32::acos(
+                    player_transform.forward().dot(
+                        (interactable_transform.translation - player_transform.translation)
+                            .with_y(0.0)
+                            .normalize_or_zero(),
+                    ),
+                );
+                if interactable_arccosine <= interactable.max_angle
+                    && interactable_arccosine < closest.1
+                {
+                    closest = (Some(interactable_entity), interactable_arccosine);
+                }
+            }
+        }
+    }
+    target.set_if_neq(InteractionTarget(closest.0));
 }
 
 fn handle_player_interaction(
     state: Res<State<PlayerState>>,
-    player_query: Query<(&Transform, &ActionState<PlayerAction>), With<Player>>,
+    player_query: Query<&ActionState<PlayerAction>, With<Player>>,
     mut dialog_runner_query: Query<&mut DialogueRunner>,
-    interactable_query: Query<(Entity, &Transform), (With<Interactable>, Without<Player>)>,
+    target: Res<InteractionTarget>,
+    interactable_query: Query<(Entity, &Interactable)>,
     mut event_writer: EventWriter<InteractEvent>,
+    mut next_player_state: ResMut<NextState<PlayerState>>,
 ) {
-    let (player_transform, action_state) = player_query.single();
+    let action_state = player_query.single();
     if action_state.just_pressed(&PlayerAction::Interact) {
         match state.get() {
             PlayerState::Free => {
-                let mut closest = (None, f32::INFINITY);
-                for (interactable_entity, interactable_transform) in interactable_query.iter() {
-                    if player_transform
-                        .translation
-                        .distance_squared(interactable_transform.translation)
-                        < (2.0 * 2.0)
-                    {
-                        let interactable_arccosine = f32::acos(
-                            player_transform.forward().dot(
-                                (interactable_transform.translation - player_transform.translation)
-                                    .normalize(),
-                            ),
-                        );
-                        if interactable_arccosine < closest.1 {
-                            closest = (Some(interactable_entity), interactable_arccosine);
-                        }
-                    }
-                }
-                if let Some(entity) = closest.0 {
-                    event_writer.send(InteractEvent(entity));
+                if let Some((entity, interactable)) =
+                    target.and_then(|entity| interactable_query.get(entity).ok())
+                {
+                    event_writer.send(InteractEvent {
+                        entity,
+                        kind: interactable.kind,
+                    });
                 }
             }
             PlayerState::Dialog => {
-                let mut dialog_runner = dialog_runner_query.single_mut();
-                dialog_runner.continue_in_next_update();
+                // Text signs are shown without a dialogue runner and close on the next press
+                if let Ok(mut dialog_runner) = dialog_runner_query.get_single_mut() {
+                    // The runner is despawned the frame after its dialogue ends
+                    if dialog_runner.is_running() {
+                        dialog_runner.continue_in_next_update();
+                    }
+                } else {
+                    next_player_state.set(PlayerState::Free);
+                }
             }
         }
     }
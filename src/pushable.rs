@@ -0,0 +1,36 @@
+use avian3d::prelude::ExternalImpulse;
+use bevy::prelude::*;
+
+use crate::{GameplaySet, InteractEvent, InteractionKind, Player};
+
+const PUSH_IMPULSE: f32 = 2.0;
+
+pub struct PushablePlugin;
+
+impl Plugin for PushablePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_push_interactions.in_set(GameplaySet));
+    }
+}
+
+#[derive(Component, Debug)]
+pub struct Pushable;
+
+fn handle_push_interactions(
+    mut pushable_query: Query<(&Transform, &mut ExternalImpulse), With<Pushable>>,
+    player_query: Query<&Transform, With<Player>>,
+    mut event_reader: EventReader<InteractEvent>,
+) {
+    for InteractEvent { entity, .. } in event_reader
+        .read()
+        .filter(|event| event.kind == InteractionKind::Push)
+    {
+        if let Ok((pushable_transform, mut impulse)) = pushable_query.get_mut(*entity) {
+            let player_transform = player_query.single();
+            let direction = (pushable_transform.translation - player_transform.translation)
+                .with_y(0.0)
+                .normalize_or_zero();
+            impulse.apply_impulse(direction * PUSH_IMPULSE);
+        }
+    }
+}
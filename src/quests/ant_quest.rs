@@ -0,0 +1,33 @@
+use bevy::prelude::*;
+
+use crate::{Npc, Player};
+
+const ANTHILL_RADIUS: f32 = 3.0;
+/// Name of the ant's NPC, which follows the player once the quest starts.
+const ANT_NAME: &str = "Ant";
+
+/// Where the ant lives, placed at the level's `Anthill` node.
+#[derive(Component, Debug)]
+pub struct Anthill;
+
+// Complete once the player has led the ant back to the anthill
+pub fn is_complete(world: &mut World) -> bool {
+    let Ok(anthill) = world
+        .query_filtered::<&GlobalTransform, With<Anthill>>()
+        .get_single(world)
+        .map(GlobalTransform::translation)
+    else {
+        return false;
+    };
+    let at_anthill =
+        |transform: &GlobalTransform| transform.translation().distance(anthill) < ANTHILL_RADIUS;
+    let player_home = world
+        .query_filtered::<&GlobalTransform, With<Player>>()
+        .iter(world)
+        .any(at_anthill);
+    let ant_home = world
+        .query_filtered::<(&Name, &GlobalTransform), With<Npc>>()
+        .iter(world)
+        .any(|(name, transform)| name.as_str() == ANT_NAME && at_anthill(transform));
+    player_home && ant_home
+}
@@ -1,14 +1,40 @@
 use core::panic;
+use std::sync::{Arc, RwLock};
 
-use bevy::{prelude::*, utils::HashMap};
+use bevy::{
+    prelude::*,
+    utils::{HashMap, HashSet},
+};
+
+use crate::YarnRegistryAppExt;
+
+mod ant_quest;
+pub use ant_quest::Anthill;
 
 pub struct QuestsPlugin;
 
 impl Plugin for QuestsPlugin {
     fn build(&self, app: &mut App) {
-        app.insert_resource(Quests::default())
+        let quests = Quests(HashMap::from_iter([(
+            "ant_quest",
+            Quest::new(ant_quest::is_complete),
+        )]));
+        let progress = QuestProgress::default();
+        app.insert_resource(quests)
+            .insert_resource(progress.clone())
             .add_event::<QuestEvents>()
-            .add_systems(Update, (start_quests, update_quest_status));
+            .add_systems(
+                Update,
+                (start_quests, update_quest_status, sync_quest_progress).chain(),
+            )
+            .add_yarn_command("start_quest", start_quest_command)
+            .add_yarn_function("quest_started", {
+                let progress = progress.clone();
+                move |name: String| progress.is_started(&name)
+            })
+            .add_yarn_function("quest_complete", move |name: String| {
+                progress.is_complete(&name)
+            });
     }
 }
 
@@ -22,7 +48,7 @@ pub enum QuestEvents {
     CompleteQuest(&'static str),
 }
 
-#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+#[derive(Debug, Clone)]
 pub struct Quest {
     pub start: bool,
     pub complete: bool,
@@ -39,12 +65,32 @@ impl Quest {
     }
 }
 
+/// Copy of the quest flags that Yarn functions can read while a dialogue is running.
+#[derive(Resource, Clone, Default)]
+pub struct QuestProgress(Arc<RwLock<QuestFlags>>);
+
+#[derive(Debug, Default)]
+struct QuestFlags {
+    started: HashSet<&'static str>,
+    complete: HashSet<&'static str>,
+}
+
+impl QuestProgress {
+    pub fn is_started(&self, name: &str) -> bool {
+        self.0.read().unwrap().started.contains(name)
+    }
+
+    pub fn is_complete(&self, name: &str) -> bool {
+        self.0.read().unwrap().complete.contains(name)
+    }
+}
+
 pub fn update_quest_status(world: &mut World) {
     if let Some(mut quests) = world.remove_resource::<Quests>() {
         for (name, quest) in quests.iter_mut() {
             if !quest.complete && (quest.predicate)(world) && quest.start {
                 quest.complete = true;
-                println!("Quest complete: {}", name);
+                info!("Quest complete: {}", name);
                 world.send_event(QuestEvents::CompleteQuest(name));
             }
         }
@@ -57,14 +103,34 @@ pub fn update_quest_status(world: &mut World) {
 // Read StartQuest event and start the quest
 pub fn start_quests(mut quests: ResMut<Quests>, mut ev_reader: EventReader<QuestEvents>) {
     for event in ev_reader.read() {
-        match event {
-            QuestEvents::StartQuest(name) => {
-                if let Some(quest) = quests.get_mut(name) {
-                    quest.start = true;
-                    println!("Quest started: {}", name);
-                }
+        if let QuestEvents::StartQuest(name) = event {
+            if let Some(quest) = quests.get_mut(name) {
+                quest.start = true;
+                info!("Quest started: {}", name);
             }
-            _ => {}
         }
     }
-}
\ No newline at end of file
+}
+
+fn sync_quest_progress(progress: Res<QuestProgress>, mut ev_reader: EventReader<QuestEvents>) {
+    for event in ev_reader.read() {
+        let mut flags = progress.0.write().unwrap();
+        match event {
+            QuestEvents::StartQuest(name) => flags.started.insert(name),
+            QuestEvents::CompleteQuest(name) => flags.complete.insert(name),
+        };
+    }
+}
+
+// <<start_quest quest_name>>
+fn start_quest_command(
+    In(name): In<String>,
+    quests: Res<Quests>,
+    mut ev_writer: EventWriter<QuestEvents>,
+) {
+    if let Some((name, _)) = quests.get_key_value(name.as_str()) {
+        ev_writer.send(QuestEvents::StartQuest(name));
+    } else {
+        warn!("Unknown quest {:?}", name);
+    }
+}
@@ -0,0 +1,213 @@
+use std::path::{Path, PathBuf};
+
+use bevy::{app::AppExit, prelude::*};
+use leafwing_input_manager::{action_state::ActionState, input_map::InputMap};
+use serde::{Deserialize, Serialize};
+
+use crate::{AppState, GameCamera, GameplaySet, Player, PlayerAction};
+
+/// Player input during one fixed tick. The camera is stored as the orbit its controls asked for,
+/// since mouse movement arrives per frame rather than per tick.
+#[derive(Serialize, Deserialize, Debug, Clone, Copy, PartialEq, Default)]
+pub struct InputFrame {
+    pub walk: Vec2,
+    pub jump: bool,
+    pub interact: bool,
+    pub yaw: f32,
+    pub pitch: f32,
+    pub distance: f32,
+}
+
+/// Input for every fixed tick from the moment the world finished loading, run-length encoded.
+#[derive(Serialize, Deserialize, Debug, Clone, Default)]
+pub struct InputRecording {
+    pub runs: Vec<(u32, InputFrame)>,
+}
+
+impl InputRecording {
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, Box<dyn std::error::Error>> {
+        let text = std::fs::read_to_string(path)?;
+        Ok(ron::de::from_str(&text)?)
+    }
+
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), Box<dyn std::error::Error>> {
+        let text = ron::ser::to_string_pretty(self, ron::ser::PrettyConfig::default())?;
+        std::fs::write(path, text)?;
+        Ok(())
+    }
+
+    pub fn push(&mut self, frame: InputFrame) {
+        match self.runs.last_mut() {
+            Some((count, last)) if *last == frame => *count += 1,
+            _ => self.runs.push((1, frame)),
+        }
+    }
+
+    pub fn frames(&self) -> Vec<InputFrame> {
+        self.runs
+            .iter()
+            .flat_map(|(count, frame)| std::iter::repeat_n(*frame, *count as usize))
+            .collect()
+    }
+}
+
+#[derive(Resource, Debug, Default)]
+pub enum InputReplay {
+    #[default]
+    Off,
+    /// Records a game, saved to `path` when it ends.
+    Recording {
+        recording: InputRecording,
+        path: Option<PathBuf>,
+    },
+    /// Plays back a recording in place of the player's input, then hands control back.
+    Replaying {
+        frames: Vec<InputFrame>,
+        tick: usize,
+    },
+}
+
+impl InputReplay {
+    pub fn replay(recording: &InputRecording) -> Self {
+        Self::Replaying {
+            frames: recording.frames(),
+            tick: 0,
+        }
+    }
+}
+
+/// The player's own input map, swapped out for an empty one while a replay drives the player.
+#[derive(Component, Debug)]
+pub struct SuspendedInputMap(InputMap<PlayerAction>);
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InputReplay>()
+            .add_systems(
+                FixedPreUpdate,
+                (record_input, replay_input).in_set(GameplaySet),
+            )
+            .add_systems(
+                Update,
+                (
+                    suspend_player_input.run_if(input_replaying),
+                    restore_player_input.run_if(not(input_replaying)),
+                ),
+            )
+            .add_systems(OnExit(AppState::InGame), save_recording)
+            .add_systems(Last, save_recording.run_if(on_event::<AppExit>()));
+    }
+}
+
+pub fn input_replaying(replay: Res<InputReplay>) -> bool {
+    matches!(*replay, InputReplay::Replaying { .. })
+}
+
+fn record_input(
+    mut replay: ResMut<InputReplay>,
+    player_query: Query<&ActionState<PlayerAction>, With<Player>>,
+    camera_query: Query<&GameCamera>,
+) {
+    let InputReplay::Recording { recording, .. } = &mut *replay else {
+        return;
+    };
+    let action_state = player_query.single();
+    let camera = camera_query.single();
+    recording.push(InputFrame {
+        walk: action_state.axis_pair(&PlayerAction::Walk),
+        jump: action_state.pressed(&PlayerAction::Jump),
+        interact: action_state.pressed(&PlayerAction::Interact),
+        yaw: camera.yaw,
+        pitch: camera.pitch,
+        distance: camera.distance,
+    });
+}
+
+fn replay_input(
+    mut replay: ResMut<InputReplay>,
+    mut player_query: Query<&mut ActionState<PlayerAction>, With<Player>>,
+) {
+    let InputReplay::Replaying { frames, tick } = &mut *replay else {
+        return;
+    };
+    let Some(frame) = frames.get(*tick) else {
+        info!("Replay finished after {} ticks", tick);
+        *replay = InputReplay::Off;
+        return;
+    };
+    let mut action_state = player_query.single_mut();
+    action_state.set_axis_pair(&PlayerAction::Walk, frame.walk);
+    for (action, pressed) in [
+        (PlayerAction::Jump, frame.jump),
+        (PlayerAction::Interact, frame.interact),
+    ] {
+        if pressed {
+            action_state.press(&action);
+        } else {
+            action_state.release(&action);
+        }
+    }
+    *tick += 1;
+}
+
+/// Sets the orbit the next tick was recorded with, before the camera follows it this frame as it
+/// did while recording.
+pub fn replay_camera(replay: Res<InputReplay>, mut camera_query: Query<&mut GameCamera>) {
+    let InputReplay::Replaying { frames, tick } = &*replay else {
+        return;
+    };
+    let Some(frame) = frames.get(*tick) else {
+        return;
+    };
+    let mut camera = camera_query.single_mut();
+    camera.yaw = frame.yaw;
+    camera.pitch = frame.pitch;
+    camera.distance = frame.distance;
+}
+
+#[allow(clippy::type_complexity)]
+fn suspend_player_input(
+    mut commands: Commands,
+    mut player_query: Query<
+        (Entity, &mut InputMap<PlayerAction>),
+        (With<Player>, Without<SuspendedInputMap>),
+    >,
+) {
+    for (entity, mut input_map) in player_query.iter_mut() {
+        let input_map = std::mem::take(&mut *input_map);
+        commands.entity(entity).insert(SuspendedInputMap(input_map));
+    }
+}
+
+fn restore_player_input(
+    mut commands: Commands,
+    mut player_query: Query<(Entity, &mut InputMap<PlayerAction>, &mut SuspendedInputMap)>,
+) {
+    for (entity, mut input_map, mut suspended) in player_query.iter_mut() {
+        *input_map = std::mem::take(&mut suspended.0);
+        commands.entity(entity).remove::<SuspendedInputMap>();
+    }
+}
+
+fn save_recording(mut replay: ResMut<InputReplay>) {
+    let InputReplay::Recording { recording, path } = &*replay else {
+        return;
+    };
+    if let Some(path) = path {
+        match recording.save(path) {
+            Ok(()) => info!(
+                "Saved {} ticks of input to {}",
+                recording.runs.iter().map(|(count, _)| count).sum::<u32>(),
+                path.display()
+            ),
+            Err(error) => error!(
+                "Couldn't save input recording to {}: {}",
+                path.display(),
+                error
+            ),
+        }
+    }
+    *replay = InputReplay::Off;
+}
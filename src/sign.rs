@@ -0,0 +1,69 @@
+use bevy::{gltf::GltfExtras, prelude::*};
+use bevy_yarnspinner::prelude::YarnProject;
+use serde::Deserialize;
+
+use crate::{
+    dialog_box::DialogBoxContent, GameplaySet, InteractEvent, InteractionKind, PlayerState,
+    YarnFunctionRegistry,
+};
+
+pub struct SignPlugin;
+
+impl Plugin for SignPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, handle_sign_interactions.in_set(GameplaySet));
+    }
+}
+
+/// A readable sign or note placed in the level, showing either plain text or a Yarn node.
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub enum Sign {
+    Text(String),
+    Node(String),
+}
+
+/// Custom properties set on `Sign_*` nodes in Blender.
+#[derive(Deserialize)]
+struct SignExtras {
+    sign_text: Option<String>,
+    sign_node: Option<String>,
+}
+
+impl Sign {
+    pub fn from_extras(extras: &GltfExtras) -> Option<Self> {
+        let extras: SignExtras = serde_json::from_str(&extras.value).ok()?;
+        extras
+            .sign_node
+            .map(Self::Node)
+            .or(extras.sign_text.map(Self::Text))
+    }
+}
+
+fn handle_sign_interactions(
+    sign_query: Query<&Sign>,
+    mut event_reader: EventReader<InteractEvent>,
+    mut next_player_state: ResMut<NextState<PlayerState>>,
+    mut dialog_box_content: ResMut<DialogBoxContent>,
+    project: Res<YarnProject>,
+    function_registry: Res<YarnFunctionRegistry>,
+    mut commands: Commands,
+) {
+    for InteractEvent { entity, .. } in event_reader
+        .read()
+        .filter(|event| event.kind == InteractionKind::Read)
+    {
+        match sign_query.get(*entity) {
+            Ok(Sign::Text(text)) => {
+                dialog_box_content.character = None;
+                dialog_box_content.line = text.clone();
+                next_player_state.set(PlayerState::Dialog);
+            }
+            Ok(Sign::Node(node)) => {
+                let mut dialog_runner = function_registry.create_dialogue_runner(&project);
+                dialog_runner.start_node(node);
+                commands.spawn(dialog_runner);
+            }
+            Err(_) => (),
+        }
+    }
+}
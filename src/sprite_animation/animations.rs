@@ -0,0 +1,86 @@
+use bevy::{
+    asset::{io::Reader, AssetLoader, AsyncReadExt, LoadContext},
+    prelude::*,
+    utils::HashMap,
+};
+use serde::Deserialize;
+
+use crate::SpriteDirections;
+
+/// Named animation clips drawn on a billboard sprite sheet.
+#[derive(Asset, TypePath, Debug)]
+pub struct SpriteAnimations {
+    pub texture: Handle<Image>,
+    /// Number of frames stacked vertically in the texture.
+    pub rows: usize,
+    /// Views side by side in the texture, for sheets drawn from several directions.
+    pub directions: Option<SpriteDirections>,
+    pub clips: HashMap<String, SpriteClip>,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpriteClip {
+    pub frames: Vec<SpriteFrame>,
+    #[serde(default = "default_looping")]
+    pub looping: bool,
+}
+
+fn default_looping() -> bool {
+    true
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct SpriteFrame {
+    pub row: usize,
+    /// Seconds the frame is shown for.
+    pub duration: f32,
+    /// Height the sprite is raised by during the frame, to bob single-frame art.
+    #[serde(default)]
+    pub lift: f32,
+}
+
+#[derive(Deserialize)]
+struct SpriteAnimationsFile {
+    texture: String,
+    rows: usize,
+    #[serde(default)]
+    directions: Option<u32>,
+    clips: HashMap<String, SpriteClip>,
+}
+
+#[derive(Default)]
+pub struct SpriteAnimationsLoader;
+
+impl AssetLoader for SpriteAnimationsLoader {
+    type Asset = SpriteAnimations;
+    type Settings = ();
+    type Error = Box<dyn std::error::Error + Send + Sync>;
+
+    async fn load<'a>(
+        &'a self,
+        reader: &'a mut Reader<'_>,
+        _settings: &'a (),
+        load_context: &'a mut LoadContext<'_>,
+    ) -> Result<SpriteAnimations, Self::Error> {
+        let mut bytes = Vec::new();
+        reader.read_to_end(&mut bytes).await?;
+        let file: SpriteAnimationsFile = ron::de::from_bytes(&bytes)?;
+        let directions = file
+            .directions
+            .map(|count| {
+                SpriteDirections::from_count(count)
+                    .ok_or_else(|| format!("Sprite sheets have 4 or 8 directions, not {}", count))
+            })
+            .transpose()?;
+        Ok(SpriteAnimations {
+            texture: load_context.load(load_context.asset_path().resolve_embed(&file.texture)?),
+            rows: file.rows,
+            directions,
+            clips: file.clips,
+        })
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["anim.ron"]
+    }
+}
@@ -0,0 +1,118 @@
+use bevy::prelude::*;
+
+use crate::{Billboard, BillboardSprites, GameplaySet};
+
+mod animations;
+pub use animations::*;
+
+pub const IDLE_CLIP: &str = "idle";
+pub const WALK_CLIP: &str = "walk";
+pub const TALK_CLIP: &str = "talk";
+
+pub struct SpriteAnimationPlugin;
+
+impl Plugin for SpriteAnimationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_asset::<SpriteAnimations>()
+            .init_asset_loader::<SpriteAnimationsLoader>()
+            .add_systems(
+                PostUpdate,
+                advance_sprite_animations
+                    .in_set(GameplaySet)
+                    .before(TransformSystem::TransformPropagate),
+            );
+    }
+}
+
+/// Plays clips from `animations` on a billboard. Other systems pick the clip with `play`.
+#[derive(Component, Debug)]
+pub struct SpriteAnimator {
+    pub animations: Handle<SpriteAnimations>,
+    clip: String,
+    frame: usize,
+    elapsed: f32,
+    /// Lift of the frame currently applied to the translation.
+    lift: f32,
+}
+
+impl SpriteAnimator {
+    pub fn new(animations: Handle<SpriteAnimations>) -> Self {
+        Self {
+            animations,
+            clip: IDLE_CLIP.to_string(),
+            frame: 0,
+            elapsed: 0.0,
+            lift: 0.0,
+        }
+    }
+
+    /// Switches to `clip` from its first frame, unless it is already playing.
+    pub fn play(&mut self, clip: &str) {
+        if self.clip != clip {
+            self.clip = clip.to_string();
+            self.frame = 0;
+            self.elapsed = 0.0;
+        }
+    }
+}
+
+fn advance_sprite_animations(
+    mut animator_query: Query<(
+        &mut SpriteAnimator,
+        &mut Billboard,
+        &mut Transform,
+        &mut Handle<StandardMaterial>,
+    )>,
+    sprite_animations: Res<Assets<SpriteAnimations>>,
+    mut sprites: BillboardSprites,
+    time: Res<Time>,
+) {
+    for (mut animator, mut billboard, mut transform, mut material) in animator_query.iter_mut() {
+        let Some(animations) = sprite_animations.get(&animator.animations) else {
+            continue;
+        };
+        let Some(clip) = animations.clips.get(&animator.clip) else {
+            continue;
+        };
+        if clip.frames.is_empty() {
+            continue;
+        }
+
+        animator.elapsed += time.delta_seconds();
+        let mut frame = animator.frame.min(clip.frames.len() - 1);
+        // Skip at most one loop of frames, which also stops at frames without a duration
+        for _ in 0..clip.frames.len() {
+            if animator.elapsed < clip.frames[frame].duration {
+                break;
+            }
+            animator.elapsed -= clip.frames[frame].duration;
+            if frame + 1 < clip.frames.len() {
+                frame += 1;
+            } else if clip.looping {
+                frame = 0;
+            } else {
+                animator.elapsed = 0.0;
+                break;
+            }
+        }
+        animator.frame = frame;
+
+        let frame = &clip.frames[frame];
+        billboard.row = frame.row;
+        billboard.rows = animations.rows;
+        // Sheets without directions leave those of the glTF node in place
+        if animations.directions.is_some() {
+            billboard.directions = animations.directions;
+        }
+        transform.translation.y += frame.lift - animator.lift;
+        animator.lift = frame.lift;
+
+        if sprites.material(&material).is_some_and(|material| {
+            material.base_color_texture.as_ref() != Some(&animations.texture)
+        }) {
+            if let Some(material) = sprites.material_mut(&mut material) {
+                material.base_color_texture = Some(animations.texture.clone());
+            }
+        }
+    }
+}
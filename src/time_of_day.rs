@@ -0,0 +1,127 @@
+use std::{
+    f32::consts::PI,
+    sync::{Arc, RwLock},
+};
+
+use bevy::{color::palettes::css, prelude::*};
+
+use crate::{AppState, GameplaySet, YarnRegistryAppExt};
+
+pub const DAY_START_HOURS: f32 = 8.0;
+/// Real seconds for a full day to pass.
+pub const DAY_LENGTH_SECONDS: f32 = 20.0 * 60.0;
+pub const SUN_ILLUMINANCE: f32 = light_consts::lux::OVERCAST_DAY;
+/// Far brighter than a real moon, so the world stays playable at night.
+pub const MOON_ILLUMINANCE: f32 = 60.0;
+pub const DAY_AMBIENT_BRIGHTNESS: f32 = 200.0;
+pub const NIGHT_AMBIENT_BRIGHTNESS: f32 = 40.0;
+const SUNRISE_COLOR: Srgba = css::ORANGE;
+const MOON_COLOR: Srgba = css::LIGHT_STEEL_BLUE;
+const NIGHT_AMBIENT_COLOR: Srgba = css::MIDNIGHT_BLUE;
+
+pub struct TimeOfDayPlugin;
+
+impl Plugin for TimeOfDayPlugin {
+    fn build(&self, app: &mut App) {
+        let clock = TimeOfDayClock::default();
+        app.init_resource::<TimeOfDay>()
+            .insert_resource(clock.clone())
+            .add_systems(OnEnter(AppState::InGame), reset_time_of_day)
+            .add_systems(
+                Update,
+                (advance_time_of_day, update_sun)
+                    .chain()
+                    .in_set(GameplaySet),
+            )
+            .add_yarn_command("set_time_of_day", set_time_of_day_command)
+            .add_yarn_function("time_of_day", move || clock.hours());
+    }
+}
+
+#[derive(Resource, Debug)]
+pub struct TimeOfDay {
+    /// Hours since midnight, from 0 to 24.
+    pub hours: f32,
+    /// In-game hours passing per real second.
+    pub speed: f32,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self {
+            hours: DAY_START_HOURS,
+            speed: 24.0 / DAY_LENGTH_SECONDS,
+        }
+    }
+}
+
+impl TimeOfDay {
+    /// Whether the time is between `from` and `to` hours, wrapping past midnight if `from` is later.
+    pub fn is_between(&self, from: f32, to: f32) -> bool {
+        if from <= to {
+            (from..to).contains(&self.hours)
+        } else {
+            self.hours >= from || self.hours < to
+        }
+    }
+}
+
+/// Copy of the time of day that Yarn functions can read while a dialogue is running.
+#[derive(Resource, Clone, Default)]
+pub struct TimeOfDayClock(Arc<RwLock<f32>>);
+
+impl TimeOfDayClock {
+    pub fn hours(&self) -> f32 {
+        *self.0.read().unwrap()
+    }
+}
+
+/// The directional light moved and coloured by the time of day, as the sun and then the moon.
+#[derive(Component, Debug)]
+pub struct Sun;
+
+fn reset_time_of_day(mut time_of_day: ResMut<TimeOfDay>) {
+    *time_of_day = TimeOfDay::default();
+}
+
+fn advance_time_of_day(
+    mut time_of_day: ResMut<TimeOfDay>,
+    clock: Res<TimeOfDayClock>,
+    time: Res<Time>,
+) {
+    time_of_day.hours = (time_of_day.hours + time_of_day.speed * time.delta_seconds()) % 24.0;
+    *clock.0.write().unwrap() = time_of_day.hours;
+}
+
+fn update_sun(
+    time_of_day: Res<TimeOfDay>,
+    mut sun_query: Query<(&mut Transform, &mut DirectionalLight), With<Sun>>,
+    mut ambient_light: ResMut<AmbientLight>,
+) {
+    // Rises in the east at 6:00, sets in the west at 18:00
+    let angle = (time_of_day.hours - 6.0) / 12.0 * PI;
+    let sun_position = Vec3::new(angle.cos(), angle.sin(), 0.4).normalize();
+    let daylight = sun_position.y.max(0.0);
+    let is_day = sun_position.y > 0.0;
+
+    for (mut transform, mut light) in sun_query.iter_mut() {
+        // The moon takes over below the horizon, from the opposite side of the sky
+        let light_position = if is_day { sun_position } else { -sun_position };
+        *transform = Transform::from_translation(light_position).looking_at(Vec3::ZERO, Vec3::Y);
+        if is_day {
+            light.illuminance = SUN_ILLUMINANCE * daylight.sqrt();
+            light.color = Color::from(SUNRISE_COLOR.mix(&css::WHITE, daylight.sqrt()));
+        } else {
+            light.illuminance = MOON_ILLUMINANCE * light_position.y.sqrt();
+            light.color = MOON_COLOR.into();
+        }
+    }
+
+    ambient_light.brightness = NIGHT_AMBIENT_BRIGHTNESS.lerp(DAY_AMBIENT_BRIGHTNESS, daylight);
+    ambient_light.color = Color::from(NIGHT_AMBIENT_COLOR.mix(&css::WHITE, daylight.sqrt()));
+}
+
+// <<set_time_of_day hours>>
+fn set_time_of_day_command(In(hours): In<f32>, mut time_of_day: ResMut<TimeOfDay>) {
+    time_of_day.hours = hours.rem_euclid(24.0);
+}
@@ -10,8 +10,7 @@ impl Plugin for DialogBoxPlugin {
         app.add_systems(
             Update,
             show_dialog_box.run_if(in_state(PlayerState::Dialog)),
-        )
-        .init_resource::<DialogBoxContent>();
+        );
     }
 }
 
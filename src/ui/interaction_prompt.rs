@@ -0,0 +1,48 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2, Pos2},
+    EguiContexts,
+};
+
+use crate::{GameCamera, GameplaySet, Interactable, InteractionTarget};
+
+/// Height above the targeted entity's origin at which the prompt is anchored.
+const PROMPT_HEIGHT: f32 = 1.5;
+
+pub struct InteractionPromptPlugin;
+
+impl Plugin for InteractionPromptPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, show_interaction_prompt.in_set(GameplaySet));
+    }
+}
+
+fn show_interaction_prompt(
+    mut egui: EguiContexts,
+    target: Res<InteractionTarget>,
+    interactable_query: Query<(&Interactable, &GlobalTransform)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<GameCamera>>,
+) {
+    let Some((interactable, interactable_transform)) =
+        target.and_then(|entity| interactable_query.get(entity).ok())
+    else {
+        return;
+    };
+    let (camera, camera_transform) = camera_query.single();
+    let Some(position) = camera.world_to_viewport(
+        camera_transform,
+        interactable_transform.translation() + Vec3::Y * PROMPT_HEIGHT,
+    ) else {
+        return;
+    };
+
+    egui::Area::new(egui::Id::new("Interaction Prompt"))
+        .pivot(Align2::CENTER_BOTTOM)
+        .fixed_pos(Pos2::new(position.x, position.y))
+        .interactable(false)
+        .show(egui.ctx_mut(), |ui| {
+            egui::Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(format!("Press E to {}", interactable.prompt));
+            });
+        });
+}
@@ -0,0 +1,116 @@
+use bevy::prelude::*;
+use bevy_egui::{
+    egui::{self, Align2},
+    EguiContexts,
+};
+use leafwing_input_manager::{
+    action_state::ActionState, input_map::InputMap, plugin::InputManagerPlugin, Actionlike,
+    InputManagerBundle,
+};
+
+use crate::{GameplaySet, Inventory, ItemCatalogParam, Player, INVENTORY_SLOTS};
+
+const ICON_SIZE: f32 = 48.0;
+
+#[derive(Actionlike, Debug, Clone, PartialEq, Eq, Hash, Reflect)]
+pub struct InventoryAction;
+
+#[derive(Component, Debug)]
+pub struct InventoryPanel;
+
+#[derive(Resource, Debug, Default)]
+pub struct InventoryPanelState {
+    pub open: bool,
+}
+
+pub struct InventoryPanelPlugin;
+
+impl Plugin for InventoryPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(InputManagerPlugin::<InventoryAction>::default())
+            .init_resource::<InventoryPanelState>()
+            .add_systems(Startup, setup_inventory_input)
+            .add_systems(
+                Update,
+                (
+                    process_inventory_input,
+                    show_inventory_panel.run_if(|state: Res<InventoryPanelState>| state.open),
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            );
+    }
+}
+
+fn setup_inventory_input(mut commands: Commands) {
+    commands.spawn((
+        InventoryPanel,
+        InputManagerBundle::with_map(InputMap::new([(InventoryAction, KeyCode::Tab)])),
+    ));
+}
+
+fn process_inventory_input(
+    inventory_panel_query: Query<&ActionState<InventoryAction>, With<InventoryPanel>>,
+    mut state: ResMut<InventoryPanelState>,
+) {
+    let inventory_action_state = inventory_panel_query.single();
+    if inventory_action_state.just_pressed(&InventoryAction) {
+        state.open = !state.open;
+    }
+}
+
+fn show_inventory_panel(
+    mut egui: EguiContexts,
+    mut state: ResMut<InventoryPanelState>,
+    inventory_query: Query<&Inventory, With<Player>>,
+    item_catalog: ItemCatalogParam,
+) {
+    let Ok(inventory) = inventory_query.get_single() else {
+        return;
+    };
+    let icons: Vec<_> = inventory
+        .stacks
+        .iter()
+        .map(|stack| {
+            item_catalog
+                .get(&stack.item)
+                .map(|definition| egui.add_image(definition.icon.clone_weak()))
+        })
+        .collect();
+
+    egui::Window::new("Inventory")
+        .pivot(Align2::RIGHT_TOP)
+        .collapsible(false)
+        .resizable(false)
+        .open(&mut state.open)
+        .show(egui.ctx_mut(), |ui| {
+            egui::Grid::new("Inventory Slots").show(ui, |ui| {
+                for (slot, (stack, icon)) in inventory.stacks.iter().zip(icons).enumerate() {
+                    let response = match icon {
+                        Some(icon) => ui.add(egui::Image::new(egui::load::SizedTexture::new(
+                            icon,
+                            [ICON_SIZE, ICON_SIZE],
+                        ))),
+                        None => ui.label(&stack.item),
+                    };
+                    ui.label(format!("x{}", stack.count));
+                    if let Some(definition) = item_catalog.get(&stack.item) {
+                        response.on_hover_ui(|ui| {
+                            ui.heading(&definition.name);
+                            ui.label(&definition.description);
+                        });
+                    }
+                    if slot % 4 == 3 {
+                        ui.end_row();
+                    }
+                }
+                for slot in inventory.stacks.len()..INVENTORY_SLOTS {
+                    ui.add_space(ICON_SIZE);
+                    ui.label("");
+                    if slot % 4 == 3 {
+                        ui.end_row();
+                    }
+                }
+            });
+        });
+}
@@ -1,6 +1,6 @@
 use bevy::{prelude::*, window::PrimaryWindow};
 use bevy_egui::{
-    egui::{self, Align, Align2, Color32, Direction, Layout, Pos2},
+    egui::{self, Align2, Pos2},
     EguiContexts,
 };
 
@@ -7,6 +7,11 @@ mod pause_menu;
 use pause_menu::*;
 pub mod dialog_box;
 use dialog_box::*;
+mod inventory;
+pub use inventory::InventoryPanelState;
+use inventory::*;
+mod interaction_prompt;
+use interaction_prompt::*;
 
 pub struct UiPlugin;
 
@@ -17,6 +22,8 @@ impl Plugin for UiPlugin {
             MainMenuPlugin,
             PauseMenuPlugin,
             DialogBoxPlugin,
+            InventoryPanelPlugin,
+            InteractionPromptPlugin,
         ));
     }
 }
@@ -0,0 +1,123 @@
+use bevy::prelude::*;
+use bevy_yarnspinner::prelude::YarnValue;
+
+use crate::{AppState, GameCamera, GameObject, GameplaySet, YarnParameter, YarnRegistryAppExt};
+
+mod particles;
+pub use particles::*;
+
+/// Half the size of the box around the camera that weather particles spawn in.
+const WEATHER_AREA: Vec3 = Vec3::new(16.0, 6.0, 16.0);
+/// Height of the weather box's centre above the camera.
+const WEATHER_HEIGHT: f32 = 6.0;
+
+pub struct WeatherPlugin;
+
+impl Plugin for WeatherPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Weather>()
+            .add_systems(Startup, setup_particle_material)
+            .add_systems(OnEnter(AppState::InGame), spawn_weather_emitter)
+            .add_systems(
+                Update,
+                (
+                    (update_weather_emitter, add_particle_meshes),
+                    simulate_particles,
+                )
+                    .chain()
+                    .in_set(GameplaySet),
+            )
+            .add_systems(
+                PostUpdate,
+                // Built from the final camera and emitter transforms of this frame
+                build_particle_meshes
+                    .in_set(GameplaySet)
+                    .after(TransformSystem::TransformPropagate),
+            )
+            .add_yarn_command("set_weather", set_weather_command);
+    }
+}
+
+#[derive(Resource, Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Weather {
+    #[default]
+    Clear,
+    Rain,
+    Leaves,
+    Pollen,
+}
+
+impl YarnParameter for Weather {
+    const TYPE_NAME: &'static str = "weather";
+
+    fn from_yarn_value(value: &YarnValue) -> Option<Self> {
+        // Weather is named after its particles, like the level's emitters
+        let particle_kind = match String::from(value.clone()).as_str() {
+            "clear" => None,
+            name => Some(ParticleKind::from_name(name)?),
+        };
+        Self::ALL
+            .into_iter()
+            .find(|weather| weather.particle_kind() == particle_kind)
+    }
+}
+
+impl Weather {
+    const ALL: [Self; 4] = [Self::Clear, Self::Rain, Self::Leaves, Self::Pollen];
+
+    pub fn particle_kind(&self) -> Option<ParticleKind> {
+        match self {
+            Self::Clear => None,
+            Self::Rain => Some(ParticleKind::Rain),
+            Self::Leaves => Some(ParticleKind::Leaves),
+            Self::Pollen => Some(ParticleKind::Pollen),
+        }
+    }
+}
+
+/// Emitter that follows the camera and spawns the particles of the current `Weather`.
+#[derive(Component, Debug)]
+pub struct WeatherEmitter;
+
+fn spawn_weather_emitter(mut commands: Commands, mut weather: ResMut<Weather>) {
+    *weather = Weather::default();
+    let mut emitter = ParticleEmitter::new(ParticleKind::Rain);
+    emitter.rate = 0.0;
+    commands.spawn((
+        GameObject,
+        WeatherEmitter,
+        emitter,
+        SpatialBundle::from_transform(Transform::from_scale(WEATHER_AREA)),
+    ));
+}
+
+fn update_weather_emitter(
+    weather: Res<Weather>,
+    camera_query: Query<&Transform, (With<GameCamera>, Without<WeatherEmitter>)>,
+    mut emitter_query: Query<(&mut ParticleEmitter, &mut Transform), With<WeatherEmitter>>,
+) {
+    let camera_translation = camera_query
+        .get_single()
+        .ok()
+        .map(|transform| transform.translation);
+    for (mut emitter, mut transform) in emitter_query.iter_mut() {
+        if let Some(camera_translation) = camera_translation {
+            transform.translation = camera_translation + Vec3::Y * WEATHER_HEIGHT;
+        }
+        if weather.is_changed() {
+            // Particles already in the air finish falling when the weather clears
+            match weather.particle_kind() {
+                Some(kind) => {
+                    emitter.kind = kind;
+                    emitter.rate = kind.default_rate();
+                }
+                None => emitter.rate = 0.0,
+            }
+        }
+    }
+}
+
+// <<set_weather kind>>
+fn set_weather_command(In(new_weather): In<Weather>, mut weather: ResMut<Weather>) {
+    *weather = new_weather;
+}
@@ -0,0 +1,247 @@
+use std::f32::consts::TAU;
+
+use bevy::{
+    color::palettes::css,
+    pbr::NotShadowCaster,
+    prelude::*,
+    render::{
+        mesh::{Indices, PrimitiveTopology},
+        render_asset::RenderAssetUsages,
+        view::NoFrustumCulling,
+    },
+};
+use rand::Rng;
+
+use crate::GameCamera;
+
+/// Most particles alive at once for a single emitter.
+pub const MAX_PARTICLES: usize = 2000;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ParticleKind {
+    Rain,
+    Leaves,
+    Pollen,
+}
+
+impl ParticleKind {
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name {
+            "rain" => Some(Self::Rain),
+            "leaves" => Some(Self::Leaves),
+            "pollen" => Some(Self::Pollen),
+            _ => None,
+        }
+    }
+
+    /// Particles emitted per second.
+    pub fn default_rate(&self) -> f32 {
+        match self {
+            Self::Rain => 400.0,
+            Self::Leaves => 4.0,
+            Self::Pollen => 20.0,
+        }
+    }
+
+    fn spawn(&self, position: Vec3, rng: &mut impl Rng) -> Particle {
+        let (velocity, lifetime, size, color) = match self {
+            // Drops are as big as the bugs
+            Self::Rain => (
+                Vec3::new(0.5, -14.0, 0.0),
+                1.5,
+                Vec2::new(0.08, 0.7),
+                css::LIGHT_BLUE.with_alpha(0.5),
+            ),
+            Self::Leaves => (
+                Vec3::new(rng.gen_range(-0.5..0.5), -0.8, rng.gen_range(-0.5..0.5)),
+                12.0,
+                Vec2::splat(rng.gen_range(0.4..0.8)),
+                css::CHOCOLATE.mix(&css::GOLDENROD, rng.gen()),
+            ),
+            Self::Pollen => (
+                Vec3::new(
+                    rng.gen_range(-0.2..0.2),
+                    rng.gen_range(-0.05..0.1),
+                    rng.gen_range(-0.2..0.2),
+                ),
+                8.0,
+                Vec2::splat(0.08),
+                css::KHAKI.with_alpha(0.8),
+            ),
+        };
+        Particle {
+            kind: *self,
+            position,
+            velocity,
+            age: 0.0,
+            lifetime,
+            size,
+            color: color.into(),
+            phase: rng.gen_range(0.0..TAU),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Particle {
+    kind: ParticleKind,
+    position: Vec3,
+    velocity: Vec3,
+    age: f32,
+    lifetime: f32,
+    size: Vec2,
+    color: LinearRgba,
+    /// Offsets the drift so particles don't move in step.
+    phase: f32,
+}
+
+impl Particle {
+    /// Extra movement on top of the particle's velocity, so leaves flutter and pollen wanders.
+    fn drift(&self) -> Vec3 {
+        let wave = self.age + self.phase;
+        match self.kind {
+            ParticleKind::Rain => Vec3::ZERO,
+            ParticleKind::Leaves => Vec3::new(wave.sin() * 1.5, (wave * 2.0).cos() * 0.3, 0.0),
+            ParticleKind::Pollen => {
+                Vec3::new((wave * 0.7).sin(), (wave * 1.3).sin(), (wave * 0.5).cos()) * 0.2
+            }
+        }
+    }
+}
+
+/// Spawns particles in the box from -1 to 1 on each axis of the entity's transform, and draws them
+/// as camera-facing quads in a single mesh on the same entity.
+#[derive(Component, Debug)]
+pub struct ParticleEmitter {
+    pub kind: ParticleKind,
+    pub rate: f32,
+    /// Fraction of a particle carried over to the next frame.
+    pending: f32,
+    particles: Vec<Particle>,
+}
+
+impl ParticleEmitter {
+    pub fn new(kind: ParticleKind) -> Self {
+        Self {
+            kind,
+            rate: kind.default_rate(),
+            pending: 0.0,
+            particles: Vec::new(),
+        }
+    }
+}
+
+#[derive(Resource, Debug)]
+pub(super) struct ParticleMaterial(Handle<StandardMaterial>);
+
+pub(super) fn setup_particle_material(
+    mut commands: Commands,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(ParticleMaterial(materials.add(StandardMaterial {
+        alpha_mode: AlphaMode::Blend,
+        unlit: true,
+        cull_mode: None,
+        ..Default::default()
+    })));
+}
+
+pub(super) fn add_particle_meshes(
+    emitter_query: Query<Entity, Added<ParticleEmitter>>,
+    material: Res<ParticleMaterial>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut commands: Commands,
+) {
+    for entity in emitter_query.iter() {
+        let mesh = Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        );
+        commands.entity(entity).insert((
+            meshes.add(mesh),
+            material.0.clone(),
+            // The mesh bounds change every frame
+            NoFrustumCulling,
+            NotShadowCaster,
+        ));
+    }
+}
+
+pub(super) fn simulate_particles(
+    mut emitter_query: Query<(&mut ParticleEmitter, &GlobalTransform)>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_seconds();
+    let mut rng = rand::thread_rng();
+    for (mut emitter, transform) in emitter_query.iter_mut() {
+        let emitter = &mut *emitter;
+        emitter.particles.retain_mut(|particle| {
+            particle.age += delta;
+            particle.position += (particle.velocity + particle.drift()) * delta;
+            particle.age < particle.lifetime
+        });
+
+        emitter.pending += emitter.rate * delta;
+        while emitter.pending >= 1.0 {
+            emitter.pending -= 1.0;
+            if emitter.particles.len() < MAX_PARTICLES {
+                let local = Vec3::new(
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
+                    rng.gen_range(-1.0..1.0),
+                );
+                let particle = emitter
+                    .kind
+                    .spawn(transform.transform_point(local), &mut rng);
+                emitter.particles.push(particle);
+            }
+        }
+    }
+}
+
+pub(super) fn build_particle_meshes(
+    camera_query: Query<&GlobalTransform, With<GameCamera>>,
+    emitter_query: Query<(&ParticleEmitter, &GlobalTransform, &Handle<Mesh>)>,
+    mut meshes: ResMut<Assets<Mesh>>,
+) {
+    let Ok(camera_transform) = camera_query.get_single() else {
+        return;
+    };
+    let (right, up) = (camera_transform.right(), camera_transform.up());
+
+    for (emitter, transform, mesh) in emitter_query.iter() {
+        let Some(mesh) = meshes.get_mut(mesh) else {
+            continue;
+        };
+        // Particles live in world space, the mesh is drawn with the emitter's transform
+        let world_to_local = transform.affine().inverse();
+        let count = emitter.particles.len();
+        let mut positions = Vec::with_capacity(count * 4);
+        let mut colors = Vec::with_capacity(count * 4);
+        let mut indices = Vec::with_capacity(count * 6);
+        for (index, particle) in emitter.particles.iter().enumerate() {
+            let half_width = right * particle.size.x * 0.5;
+            let half_height = up * particle.size.y * 0.5;
+            // Fade out over the last second
+            let fade = (particle.lifetime - particle.age).min(1.0);
+            let color = particle
+                .color
+                .with_alpha(particle.color.alpha * fade)
+                .to_f32_array();
+            for corner in [
+                particle.position - half_width - half_height,
+                particle.position + half_width - half_height,
+                particle.position + half_width + half_height,
+                particle.position - half_width + half_height,
+            ] {
+                positions.push(world_to_local.transform_point3(corner).to_array());
+                colors.push(color);
+            }
+            let first = index as u32 * 4;
+            indices.extend([first, first + 1, first + 2, first, first + 2, first + 3]);
+        }
+        mesh.insert_attribute(Mesh::ATTRIBUTE_POSITION, positions);
+        mesh.insert_attribute(Mesh::ATTRIBUTE_COLOR, colors);
+        mesh.insert_indices(Indices::U32(indices));
+    }
+}
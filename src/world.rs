@@ -1,24 +1,80 @@
-use std::f32::consts::PI;
-
 use avian3d::{
-    collision::{ColliderConstructor, ColliderConstructorHierarchy},
-    prelude::RigidBody,
+    collision::{Collider, ColliderConstructor, ColliderConstructorHierarchy},
+    prelude::{ExternalImpulse, Physics, PhysicsTime, RigidBody},
 };
-use bevy::{
-    pbr::NotShadowCaster, prelude::*, render::mesh::PlaneMeshBuilder
+use bevy::{gltf::GltfExtras, prelude::*, render::view::RenderLayers, sprite::Anchor};
+
+use crate::{
+    game_asset, item_catalog_loaded, Anthill, AppState, Billboard, BillboardExtras,
+    BillboardSprite, BillboardSprites, BlobShadow, Bugoid, CameraZone, CutoutShadow, Door,
+    GameObject, GameplaySet, Interactable, InteractionKind, ItemCatalogParam, Npc, NpcSchedule,
+    ParticleEmitter, ParticleKind, PausedState, Pickup, Pushable, Sign, SpriteAnimator,
+    SpriteShadow, Sun, YarnRegistryAppExt, SHADOW_PROXY_LAYER,
 };
 
-use crate::{AppState, Billboard, GameObject, GameplaySet, Interactable, Npc};
+const PEBBLE_RADIUS: f32 = 0.4;
+const ANTHILL_MOUND_RADIUS: f32 = 1.2;
+const ANTHILL_MOUND_HEIGHT: f32 = 0.8;
 
 pub struct WorldPlugin;
 
 impl Plugin for WorldPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(OnEnter(AppState::InGame), setup_world)
-            .add_systems(Update, handle_world_load.in_set(GameplaySet));
+        app.init_resource::<WorldLoaded>()
+            .init_resource::<PebbleAssets>()
+            .add_systems(OnEnter(AppState::InGame), setup_world)
+            // Nodes are only seen once, when they are added, so wait until pickups can be defined
+            .add_systems(
+                Update,
+                handle_world_load
+                    .in_set(GameplaySet)
+                    .run_if(item_catalog_loaded),
+            )
+            .add_systems(Update, (update_world_loaded, pause_physics).chain())
+            .add_yarn_command("spawn", spawn_command);
+    }
+}
+
+/// The level scene, whose colliders are built once all of its meshes have loaded.
+#[derive(Component, Debug)]
+pub struct WorldScene;
+
+/// Spawned by `handle_world_load` for a node of the level, rather than being part of its scene.
+#[derive(Component, Debug)]
+pub struct LevelObject;
+
+/// Mesh and material shared by every pebble.
+#[derive(Resource, Debug)]
+struct PebbleAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+impl FromWorld for PebbleAssets {
+    fn from_world(world: &mut World) -> Self {
+        let mesh = world
+            .resource_mut::<Assets<Mesh>>()
+            .add(Sphere::new(PEBBLE_RADIUS).mesh().ico(2).unwrap());
+        let material = world
+            .resource_mut::<Assets<StandardMaterial>>()
+            .add(StandardMaterial {
+                base_color: Color::srgb(0.5, 0.5, 0.5),
+                perceptual_roughness: 1.0,
+                ..Default::default()
+            });
+        Self { mesh, material }
     }
 }
 
+/// Whether the level's colliders exist. Physics waits for them, so nothing falls through the
+/// ground while the level loads and runs play out the same however long loading takes.
+#[derive(Resource, Debug, Default)]
+pub struct WorldLoaded(pub bool);
+
+pub fn world_loaded(world_loaded: Res<WorldLoaded>) -> bool {
+    world_loaded.0
+}
+
 fn setup_world(mut commands: Commands, asset_server: Res<AssetServer>) {
 
     commands.spawn((
@@ -33,51 +89,233 @@ fn setup_world(mut commands: Commands, asset_server: Res<AssetServer>) {
             },
             ..Default::default()
         },
+        RenderLayers::default().with(SHADOW_PROXY_LAYER),
+        Sun,
     ));
+    spawn_world_scene(&mut commands, &asset_server);
+}
+
+/// Spawns the level, whose named nodes are handled by `handle_world_load` once it is in the world.
+pub fn spawn_world_scene(commands: &mut Commands, asset_server: &AssetServer) {
     commands.spawn((
         GameObject,
         SceneBundle {
-            scene: asset_server.load(
-                GltfAssetLabel::Scene(0).from_asset("embedded://ludum_dare_56/models/world.glb"),
-            ),
+            scene: asset_server
+                .load(GltfAssetLabel::Scene(0).from_asset(game_asset("models/world.glb"))),
             ..Default::default()
         },
         ColliderConstructorHierarchy::new(ColliderConstructor::TrimeshFromMesh),
         RigidBody::Static,
+        WorldScene,
     ));
 }
 
+fn update_world_loaded(
+    scene_query: Query<(), (With<WorldScene>, Without<ColliderConstructorHierarchy>)>,
+    mut world_loaded: ResMut<WorldLoaded>,
+) {
+    let loaded = !scene_query.is_empty();
+    if world_loaded.0 != loaded {
+        world_loaded.0 = loaded;
+    }
+}
+
+/// Stops physics while the level loads and while the game is paused.
+fn pause_physics(
+    world_loaded: Res<WorldLoaded>,
+    paused_state: Res<State<PausedState>>,
+    mut physics_time: ResMut<Time<Physics>>,
+) {
+    let paused = !world_loaded.0 || *paused_state.get() == PausedState::Paused;
+    if paused != physics_time.is_paused() {
+        if paused {
+            physics_time.pause();
+        } else {
+            physics_time.unpause();
+        }
+    }
+}
+
 fn handle_world_load(
     mut commands: Commands,
-    new_world_object_query: Query<(&Transform, &Name), Added<Name>>,
+    new_world_object_query: Query<(Entity, &Transform, &Name, Option<&GltfExtras>), Added<Name>>,
     asset_server: Res<AssetServer>,
+    item_catalog: ItemCatalogParam,
+    pebble_assets: Res<PebbleAssets>,
+    mut sprites: BillboardSprites,
 ) {
-    for (transform, name) in new_world_object_query.iter() {
+    for (entity, transform, name, extras) in new_world_object_query.iter() {
         match name.as_str() {
             "Ant_Spawn" => {
-                let ant_texture =
-                    asset_server.load("embedded://ludum_dare_56/textures/ant.png");
+                let billboard_extras = extras.map(BillboardExtras::from_extras).unwrap_or_default();
+                let ant_texture = asset_server.load(game_asset("textures/ant.png"));
+                let ant_animations = asset_server.load(game_asset(&format!(
+                    "animations/{}",
+                    billboard_extras
+                        .sprite_animations
+                        .as_deref()
+                        .unwrap_or("ant.anim.ron")
+                )));
 
+                let mut ant = commands.spawn((
+                    sprites
+                        .bundle(
+                            &BillboardSprite::new(ant_texture)
+                                .with_size(Vec2::splat(2.0))
+                                .with_anchor(Anchor::BottomCenter),
+                        )
+                        .with_billboard(
+                            Billboard::default()
+                                .with_mode(billboard_extras.billboard_mode.unwrap_or_default())
+                                .with_directions(billboard_extras.directions())
+                                .with_facing(transform.forward()),
+                        )
+                        .with_transform(*transform),
+                    SpriteAnimator::new(ant_animations),
+                    Name::new("Ant"),
+                    Bugoid,
+                    Npc("Ant_Start".to_string()),
+                    Interactable::new(InteractionKind::Talk),
+                    LevelObject,
+                ));
+                if let Some(schedule) = extras.and_then(NpcSchedule::from_extras) {
+                    ant.insert(schedule);
+                }
+                match billboard_extras.sprite_shadow.unwrap_or_default() {
+                    SpriteShadow::None => (),
+                    SpriteShadow::Blob => {
+                        ant.insert(BlobShadow { radius: 0.6 });
+                    }
+                    SpriteShadow::Cutout => {
+                        ant.insert(CutoutShadow);
+                    }
+                }
+            }
+            "Anthill" => {
                 commands.spawn((
-                    Billboard,
+                    GameObject,
                     PbrBundle {
-                        mesh: asset_server
-                            .add(PlaneMeshBuilder::new(Dir3::NEG_Z, Vec2::splat(2.0)).build().rotated_by(Quat::from_axis_angle(Vec3::NEG_Z, PI))),
+                        mesh: asset_server.add(
+                            Mesh::from(Cone {
+                                radius: ANTHILL_MOUND_RADIUS,
+                                height: ANTHILL_MOUND_HEIGHT,
+                            })
+                            .translated_by(Vec3::Y * ANTHILL_MOUND_HEIGHT / 2.0),
+                        ),
                         material: asset_server.add(StandardMaterial {
-                            base_color_texture: Some(ant_texture),
-                            alpha_mode: AlphaMode::Blend,
-                            unlit: true,
+                            base_color: Color::srgb(0.45, 0.3, 0.2),
+                            perceptual_roughness: 1.0,
                             ..Default::default()
                         }),
-                        transform: *transform * Transform::from_translation(Vec3::Y),
+                        transform: *transform,
                         ..Default::default()
                     },
-                    Npc("Ant_Start".to_string()),
-                    Interactable,
-                    NotShadowCaster,
+                    Anthill,
+                    LevelObject,
+                ));
+            }
+            item if item.starts_with("Item_") => {
+                let item = without_duplicate_suffix(item.trim_start_matches("Item_"));
+                let Some(definition) = item_catalog.get(item) else {
+                    warn!("Unknown item {:?} in node {:?}", item, name.as_str());
+                    continue;
+                };
+
+                commands.spawn((
+                    GameObject,
+                    sprites
+                        .bundle(
+                            &BillboardSprite::new(definition.icon.clone())
+                                .with_anchor(Anchor::BottomCenter),
+                        )
+                        .with_transform(*transform),
+                    Interactable::new(InteractionKind::PickUp)
+                        .with_prompt(format!("pick up {}", definition.name)),
+                    Pickup {
+                        item: item.to_string(),
+                        count: 1,
+                    },
+                    LevelObject,
+                ));
+            }
+            pebble if pebble.starts_with("Pebble_") => {
+                commands.spawn((
+                    GameObject,
+                    PbrBundle {
+                        mesh: pebble_assets.mesh.clone(),
+                        material: pebble_assets.material.clone(),
+                        transform: *transform,
+                        ..Default::default()
+                    },
+                    RigidBody::Dynamic,
+                    Collider::sphere(PEBBLE_RADIUS),
+                    ExternalImpulse::default(),
+                    Pushable,
+                    Interactable::new(InteractionKind::Push),
+                    LevelObject,
                 ));
             }
+            door if door.starts_with("Door_") => {
+                commands.entity(entity).insert((
+                    Door {
+                        destination: without_duplicate_suffix(door.trim_start_matches("Door_"))
+                            .to_string(),
+                    },
+                    Interactable::new(InteractionKind::UseDoor),
+                ));
+            }
+            sign if sign.starts_with("Sign_") => {
+                let Some(sign) = extras.and_then(Sign::from_extras) else {
+                    warn!("Sign {:?} has no sign_text or sign_node property", sign);
+                    continue;
+                };
+                commands
+                    .entity(entity)
+                    .insert((sign, Interactable::new(InteractionKind::Read)));
+            }
+            zone if zone.starts_with("CameraZone_") => {
+                let Some(camera_zone) = extras.and_then(CameraZone::from_extras) else {
+                    warn!("Camera zone {:?} has no valid camera_mode property", zone);
+                    continue;
+                };
+                commands.entity(entity).insert(camera_zone);
+            }
+            emitter if emitter.starts_with("Emitter_") => {
+                let kind = without_duplicate_suffix(emitter.trim_start_matches("Emitter_"));
+                let Some(kind) = ParticleKind::from_name(&kind.to_lowercase()) else {
+                    warn!("Unknown particle kind {:?} in node {:?}", kind, emitter);
+                    continue;
+                };
+                commands.entity(entity).insert(ParticleEmitter::new(kind));
+            }
             _ => (),
         }
     }
 }
+
+// <<spawn node location>> places a level node like `Item_seed` at the node named `location`
+fn spawn_command(
+    In((node, location)): In<(String, String)>,
+    location_query: Query<(&Name, &GlobalTransform)>,
+    mut commands: Commands,
+) {
+    let Some((_, location_transform)) = location_query
+        .iter()
+        .find(|(name, _)| name.as_str() == location)
+    else {
+        warn!("Unknown location {:?}", location);
+        return;
+    };
+    commands.spawn((
+        GameObject,
+        Name::new(node),
+        SpatialBundle::from_transform(Transform::from_translation(
+            location_transform.translation(),
+        )),
+    ));
+}
+
+/// `name` without the ".001" etc. that Blender suffixes duplicated nodes with.
+fn without_duplicate_suffix(name: &str) -> &str {
+    name.split_once('.').map_or(name, |(name, _)| name)
+}
@@ -0,0 +1,75 @@
+mod common;
+
+use bevy::prelude::*;
+use common::GameHarness;
+use ludum_dare_56::{Npc, NpcSchedule, QuestProgress};
+
+const ANT_NODE: &str = "Ant_Start";
+/// Frames the ant gets to catch up with the player, since it walks slower.
+const ANT_FOLLOW_FRAMES: usize = 900;
+
+#[test]
+fn ant_quest_starts_after_talking_to_the_ant() {
+    let mut harness = GameHarness::new();
+    assert!(!harness.quest("ant_quest").start);
+
+    harness.talk_to(ANT_NODE);
+    harness.update();
+
+    let quest = harness.quest("ant_quest");
+    assert!(quest.start);
+    assert!(!quest.complete);
+    assert!(harness
+        .world()
+        .resource::<QuestProgress>()
+        .is_started("ant_quest"));
+}
+
+#[test]
+fn ant_quest_completes_once_the_ant_is_led_to_the_anthill() {
+    let mut harness = GameHarness::new();
+    harness.talk_to(ANT_NODE);
+
+    let anthill = harness.anthill_translation();
+    assert!(harness.walk_to(anthill, 1.0), "couldn't reach the anthill");
+    assert!(
+        harness.update_until(ANT_FOLLOW_FRAMES, |world| {
+            world.resource::<QuestProgress>().is_complete("ant_quest")
+        }),
+        "the ant didn't follow the player to the anthill"
+    );
+
+    assert!(harness.quest("ant_quest").complete);
+}
+
+#[test]
+fn ant_quest_does_not_complete_without_the_ant() {
+    let mut harness = GameHarness::new();
+    harness.talk_to(ANT_NODE);
+    let world = harness.world();
+    let ant = world.query_filtered::<Entity, With<Npc>>().single(world);
+    world
+        .entity_mut(ant)
+        .insert(NpcSchedule::staying_at("Ant_Spawn"));
+
+    let anthill = harness.anthill_translation();
+    assert!(harness.walk_to(anthill, 1.0), "couldn't reach the anthill");
+    harness.update_frames(ANT_FOLLOW_FRAMES);
+
+    let quest = harness.quest("ant_quest");
+    assert!(quest.start);
+    assert!(!quest.complete);
+}
+
+#[test]
+fn ant_quest_does_not_complete_before_it_starts() {
+    let mut harness = GameHarness::new();
+
+    let anthill = harness.anthill_translation();
+    assert!(harness.walk_to(anthill, 1.0), "couldn't reach the anthill");
+    harness.update();
+
+    let quest = harness.quest("ant_quest");
+    assert!(!quest.start);
+    assert!(!quest.complete);
+}
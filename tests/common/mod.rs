@@ -0,0 +1,296 @@
+//! Drives a headless game from integration tests, through the same inputs a player would use.
+// Each test binary uses its own part of the harness
+#![allow(dead_code)]
+
+use std::time::Duration;
+
+use bevy::{gltf::GltfExtras, prelude::*, utils::HashSet};
+use bevy_yarnspinner::prelude::DialogueRunner;
+use leafwing_input_manager::user_input::Buttonlike;
+use ludum_dare_56::{
+    headless::{headless_app, wait_for_game_assets},
+    Anthill, AppState, GameCamera, InputRecording, InputReplay, Npc, Player, PlayerState, Quest,
+    Quests, WorldLoaded,
+};
+
+/// Longest the game's assets may take to load.
+const LOAD_TIMEOUT: Duration = Duration::from_secs(60);
+/// Frames to wait for the level to be spawned once its assets have loaded.
+const LOAD_FRAMES: usize = 60;
+/// Frames the player gets to walk anywhere before giving up.
+const WALK_FRAMES: usize = 1200;
+/// Frames a dialogue may take before giving up.
+const DIALOGUE_FRAMES: usize = 600;
+/// Distance the player talks to NPCs from.
+const TALK_DISTANCE: f32 = 1.0;
+/// Horizontal walking directions this far off the target still count as heading towards it.
+const WALK_DIRECTION_THRESHOLD: f32 = 0.3;
+
+pub struct GameHarness {
+    pub app: App,
+    held_keys: HashSet<KeyCode>,
+}
+
+impl GameHarness {
+    /// Starts a game and waits for the world and its NPCs to be spawned.
+    pub fn new() -> Self {
+        Self::with_replay(InputReplay::Off)
+    }
+
+    /// Starts a game whose input is recorded from the moment the world loads.
+    pub fn recording() -> Self {
+        Self::with_replay(InputReplay::Recording {
+            recording: InputRecording::default(),
+            path: None,
+        })
+    }
+
+    /// Starts a game driven by `recording` until it runs out.
+    pub fn replaying(recording: &InputRecording) -> Self {
+        Self::with_replay(InputReplay::replay(recording))
+    }
+
+    fn with_replay(replay: InputReplay) -> Self {
+        let mut harness = Self {
+            app: headless_app(),
+            held_keys: HashSet::new(),
+        };
+        harness.world().insert_resource(replay);
+        harness.update();
+        harness
+            .world()
+            .resource_mut::<NextState<AppState>>()
+            .set(AppState::InGame);
+        assert!(
+            wait_for_game_assets(&mut harness.app, LOAD_TIMEOUT),
+            "the game's assets didn't load"
+        );
+        assert!(
+            harness.update_until(LOAD_FRAMES, |world| {
+                world.resource::<WorldLoaded>().0
+                    && world.query::<&Npc>().iter(world).next().is_some()
+            }),
+            "the world didn't load"
+        );
+        // Let the player land on the ground
+        harness.update_frames(60);
+        harness
+    }
+
+    /// Stops recording, returning the input recorded so far.
+    pub fn take_recording(&mut self) -> InputRecording {
+        match std::mem::take(&mut *self.world().resource_mut::<InputReplay>()) {
+            InputReplay::Recording { recording, .. } => recording,
+            replay => panic!("not recording, but {:?}", replay),
+        }
+    }
+
+    /// Updates until the replay has run out, returning whether it did within `max_frames`.
+    pub fn finish_replay(&mut self, max_frames: usize) -> bool {
+        self.update_until(max_frames, |world| {
+            !matches!(
+                world.resource::<InputReplay>(),
+                InputReplay::Replaying { .. }
+            )
+        })
+    }
+
+    pub fn world(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn update(&mut self) {
+        self.app.update();
+    }
+
+    pub fn update_frames(&mut self, frames: usize) {
+        for _ in 0..frames {
+            self.update();
+        }
+    }
+
+    /// Updates until `condition` holds, returning whether it did within `max_frames`.
+    pub fn update_until(
+        &mut self,
+        max_frames: usize,
+        mut condition: impl FnMut(&mut World) -> bool,
+    ) -> bool {
+        for _ in 0..max_frames {
+            if condition(self.world()) {
+                return true;
+            }
+            self.update();
+        }
+        condition(self.world())
+    }
+
+    /// Holds exactly `keys` down from the next update on.
+    pub fn hold_keys(&mut self, keys: &[KeyCode]) {
+        let keys: HashSet<_> = keys.iter().copied().collect();
+        for key in self
+            .held_keys
+            .difference(&keys)
+            .copied()
+            .collect::<Vec<_>>()
+        {
+            key.release(self.world());
+        }
+        for key in keys
+            .difference(&self.held_keys)
+            .copied()
+            .collect::<Vec<_>>()
+        {
+            key.press(self.world());
+        }
+        self.held_keys = keys;
+    }
+
+    /// Presses and releases `key` over two updates.
+    pub fn tap(&mut self, key: KeyCode) {
+        key.press(self.world());
+        self.update();
+        key.release(self.world());
+        self.update();
+    }
+
+    /// Adds a level node named `name` at `translation`, set up as if it came from `world.glb`.
+    pub fn spawn_node(&mut self, name: &str, translation: Vec3, extras: Option<&str>) -> Entity {
+        let mut node = self.world().spawn((
+            Name::new(name.to_string()),
+            SpatialBundle::from_transform(Transform::from_translation(translation)),
+        ));
+        if let Some(extras) = extras {
+            node.insert(GltfExtras {
+                value: extras.to_string(),
+            });
+        }
+        let entity = node.id();
+        self.update();
+        entity
+    }
+
+    pub fn player_translation(&mut self) -> Vec3 {
+        let world = self.world();
+        world
+            .query_filtered::<&GlobalTransform, With<Player>>()
+            .single(world)
+            .translation()
+    }
+
+    /// Position of the NPC whose dialogue starts at `node`.
+    pub fn npc_translation(&mut self, node: &str) -> Vec3 {
+        let world = self.world();
+        world
+            .query::<(&Npc, &GlobalTransform)>()
+            .iter(world)
+            .find(|(npc, _)| npc.0 == node)
+            .map(|(_, transform)| transform.translation())
+            .unwrap_or_else(|| panic!("no NPC with node {:?}", node))
+    }
+
+    pub fn anthill_translation(&mut self) -> Vec3 {
+        let world = self.world();
+        world
+            .query_filtered::<&GlobalTransform, With<Anthill>>()
+            .single(world)
+            .translation()
+    }
+
+    /// Walks with WASD until the player is within `distance` of `target` horizontally, returning
+    /// whether it got there.
+    pub fn walk_to(&mut self, target: Vec3, distance: f32) -> bool {
+        for _ in 0..WALK_FRAMES {
+            let offset = (target - self.player_translation()).with_y(0.0);
+            if offset.length() < distance {
+                self.hold_keys(&[]);
+                // Come to a stop
+                self.update_frames(30);
+                return true;
+            }
+
+            let world = self.world();
+            let camera_transform = world
+                .query_filtered::<&Transform, With<GameCamera>>()
+                .single(world);
+            let direction = offset.normalize();
+            let forward = direction.dot(camera_transform.forward().with_y(0.0).normalize());
+            let right = direction.dot(camera_transform.right().with_y(0.0).normalize());
+            let mut keys = Vec::new();
+            if forward > WALK_DIRECTION_THRESHOLD {
+                keys.push(KeyCode::KeyW);
+            } else if forward < -WALK_DIRECTION_THRESHOLD {
+                keys.push(KeyCode::KeyS);
+            }
+            if right > WALK_DIRECTION_THRESHOLD {
+                keys.push(KeyCode::KeyD);
+            } else if right < -WALK_DIRECTION_THRESHOLD {
+                keys.push(KeyCode::KeyA);
+            }
+            self.hold_keys(&keys);
+            self.update();
+        }
+        self.hold_keys(&[]);
+        false
+    }
+
+    /// Walks up to the NPC whose dialogue starts at `node` and talks to them until the dialogue
+    /// ends.
+    pub fn talk_to(&mut self, node: &str) {
+        let npc = self.npc_translation(node);
+        assert!(
+            self.walk_to(npc, TALK_DISTANCE),
+            "couldn't reach the NPC at {}",
+            node
+        );
+        self.interact();
+        assert!(
+            self.dialogue_running(),
+            "talking to the NPC at {} didn't start a dialogue",
+            node
+        );
+        // The dialogue takes over once its first line is presented
+        assert!(self.update_until(10, |world| {
+            *world.resource::<State<PlayerState>>().get() == PlayerState::Dialog
+        }));
+        assert!(
+            self.finish_dialogue(),
+            "the dialogue at {} didn't end",
+            node
+        );
+        assert_eq!(self.player_state(), PlayerState::Free);
+    }
+
+    pub fn interact(&mut self) {
+        self.tap(KeyCode::KeyE);
+    }
+
+    pub fn player_state(&mut self) -> PlayerState {
+        self.world().resource::<State<PlayerState>>().get().clone()
+    }
+
+    pub fn dialogue_running(&mut self) -> bool {
+        let world = self.world();
+        world
+            .query::<&DialogueRunner>()
+            .iter(world)
+            .next()
+            .is_some()
+    }
+
+    /// Continues every line of the running dialogue until it ends, returning whether it did.
+    pub fn finish_dialogue(&mut self) -> bool {
+        for _ in 0..DIALOGUE_FRAMES {
+            if !self.dialogue_running() {
+                // The player state switches back the frame after
+                self.update();
+                return true;
+            }
+            self.interact();
+        }
+        false
+    }
+
+    pub fn quest(&mut self, name: &str) -> Quest {
+        self.world().resource::<Quests>()[name].clone()
+    }
+}
@@ -0,0 +1,123 @@
+mod common;
+
+use bevy::prelude::*;
+use common::GameHarness;
+use ludum_dare_56::{
+    dialog_box::DialogBoxContent, Inventory, Pickup, Player, PlayerState, Pushable,
+};
+
+/// Distance the player interacts with level objects from.
+const INTERACT_DISTANCE: f32 = 1.0;
+
+/// Distance in front of the player that pebbles are dropped at.
+const PEBBLE_DISTANCE: f32 = 1.0;
+/// Frames a dropped pebble gets to land.
+const SETTLE_FRAMES: usize = 30;
+/// Frames a pushed pebble gets to roll.
+const ROLL_FRAMES: usize = 30;
+/// Open ground a few steps ahead of where the player starts.
+const OPEN_GROUND: Vec3 = Vec3::new(0.0, 0.0, 3.0);
+
+#[test]
+fn picking_up_an_item_adds_it_to_the_inventory() {
+    let mut harness = GameHarness::new();
+    let translation = harness.player_translation() + OPEN_GROUND;
+    harness.spawn_node("Item_seed.001", translation, None);
+    let world = harness.world();
+    assert_eq!(world.query::<&Pickup>().iter(world).count(), 1);
+
+    assert!(harness.walk_to(translation, INTERACT_DISTANCE));
+    harness.interact();
+
+    let world = harness.world();
+    let inventory = world
+        .query_filtered::<&Inventory, With<Player>>()
+        .single(world);
+    assert_eq!(inventory.count("seed"), 1);
+    assert_eq!(world.query::<&Pickup>().iter(world).count(), 0);
+}
+
+#[test]
+fn pushing_a_pebble_rolls_it_away() {
+    let mut harness = GameHarness::new();
+    let start = harness.player_translation();
+    assert!(harness.walk_to(start + OPEN_GROUND, INTERACT_DISTANCE));
+    // Walking into the pebble would knock it around or climb onto it, so drop it in front instead
+    let player_transform = player_transform(&mut harness);
+    harness.spawn_node(
+        "Pebble_001",
+        player_transform.translation + player_transform.forward() * PEBBLE_DISTANCE,
+        None,
+    );
+    harness.update_frames(SETTLE_FRAMES);
+
+    let pebble_before = pebble_translation(&mut harness);
+    harness.interact();
+    harness.update_frames(ROLL_FRAMES);
+
+    let pebble_after = pebble_translation(&mut harness);
+    let pushed = (pebble_after - pebble_before).with_y(0.0);
+    assert!(
+        pushed.dot(*player_transform.forward()) > 0.5,
+        "the pebble only moved from {} to {}",
+        pebble_before,
+        pebble_after
+    );
+}
+
+#[test]
+fn using_a_door_moves_the_player_to_its_destination() {
+    let mut harness = GameHarness::new();
+    let start = harness.player_translation();
+    let destination = start + Vec3::new(-3.0, 0.0, 0.0);
+    harness.spawn_node("Garden", destination, None);
+    harness.spawn_node("Door_Garden.001", start + OPEN_GROUND, None);
+
+    assert!(harness.walk_to(start + OPEN_GROUND, INTERACT_DISTANCE));
+    harness.interact();
+
+    let offset = (harness.player_translation() - destination).with_y(0.0);
+    assert!(
+        offset.length() < INTERACT_DISTANCE,
+        "the player is at {} rather than {}",
+        harness.player_translation(),
+        destination
+    );
+}
+
+#[test]
+fn reading_a_text_sign_shows_it_until_closed() {
+    let mut harness = GameHarness::new();
+    let translation = harness.player_translation() + OPEN_GROUND;
+    harness.spawn_node(
+        "Sign_Welcome",
+        translation,
+        Some(r#"{"sign_text": "Welcome to the garden"}"#),
+    );
+
+    assert!(harness.walk_to(translation, INTERACT_DISTANCE));
+    harness.interact();
+    assert_eq!(harness.player_state(), PlayerState::Dialog);
+    let content = harness.world().resource::<DialogBoxContent>();
+    assert_eq!(content.character, None);
+    assert_eq!(content.line, "Welcome to the garden");
+    assert!(!harness.dialogue_running());
+
+    harness.interact();
+    assert_eq!(harness.player_state(), PlayerState::Free);
+}
+
+fn pebble_translation(harness: &mut GameHarness) -> Vec3 {
+    let world = harness.world();
+    world
+        .query_filtered::<&GlobalTransform, With<Pushable>>()
+        .single(world)
+        .translation()
+}
+
+fn player_transform(harness: &mut GameHarness) -> Transform {
+    let world = harness.world();
+    *world
+        .query_filtered::<&Transform, With<Player>>()
+        .single(world)
+}
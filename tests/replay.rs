@@ -0,0 +1,61 @@
+mod common;
+
+use common::GameHarness;
+use ludum_dare_56::{InputRecording, PlayerState};
+
+const ANT_NODE: &str = "Ant_Start";
+/// Frames a replay gets beyond its own length to finish, for frames spent outside gameplay.
+const REPLAY_SLACK_FRAMES: usize = 600;
+/// Replays run the same physics steps on the same input, so positions only differ by rounding.
+const POSITION_TOLERANCE: f32 = 1e-3;
+
+fn replay_frames(recording: &InputRecording) -> usize {
+    recording.frames().len() + REPLAY_SLACK_FRAMES
+}
+
+#[test]
+fn replaying_a_recording_ends_where_it_did() {
+    let mut harness = GameHarness::recording();
+    let ant = harness.npc_translation(ANT_NODE);
+    assert!(harness.walk_to(ant, 1.0), "couldn't reach the ant");
+    let recording = harness.take_recording();
+    let recorded_translation = harness.player_translation();
+
+    let mut harness = GameHarness::replaying(&recording);
+    assert!(harness.finish_replay(replay_frames(&recording)));
+    let replayed_translation = harness.player_translation();
+
+    assert!(
+        recorded_translation.distance(replayed_translation) < POSITION_TOLERANCE,
+        "the replay ended at {} rather than {}",
+        replayed_translation,
+        recorded_translation
+    );
+}
+
+/// Guards the walk to the ant and its dialogue, recorded from `GameHarness::talk_to`.
+#[test]
+fn ant_quest_replay_starts_the_quest() {
+    let recording = InputRecording::load("tests/replays/ant_quest_start.ron").unwrap();
+    let mut harness = GameHarness::replaying(&recording);
+    assert!(!harness.quest("ant_quest").start);
+
+    assert!(harness.finish_replay(replay_frames(&recording)));
+    harness.update();
+
+    assert!(harness.quest("ant_quest").start);
+    assert_eq!(harness.player_state(), PlayerState::Free);
+    assert!(!harness.dialogue_running());
+}
+
+/// Rewrites the checked-in replay, run with `cargo test --test replay -- --ignored`.
+#[test]
+#[ignore]
+fn record_ant_quest_replay() {
+    let mut harness = GameHarness::recording();
+    harness.talk_to(ANT_NODE);
+    harness
+        .take_recording()
+        .save("tests/replays/ant_quest_start.ron")
+        .unwrap();
+}